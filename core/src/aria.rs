@@ -0,0 +1,191 @@
+//! Typed ARIA attributes.
+//!
+//! [`AriaApi`] sets the `role` and `aria-*` attributes that assistive
+//! technologies use to understand custom widgets, e.g. a `div` that acts as a
+//! slider:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! let slider: Node<()> = html::div()
+//!     .role(Role::Slider)
+//!     .aria_valuemin(0.0)
+//!     .aria_valuemax(100.0)
+//!     .aria_valuenow(40.0)
+//!     .aria_label("Volume");
+//!
+//! assert_eq!(slider.get_attr("role"), Some("slider"));
+//! assert_eq!(slider.get_attr("aria-valuenow"), Some("40"));
+//! ```
+
+use crate::{html::attr_values, prelude::*};
+use std::{fmt, str::FromStr};
+
+pub use crate::html::AttrValueError;
+
+attr_values! {
+    /// WAI-ARIA roles
+    ///
+    /// See <https://www.w3.org/TR/wai-aria/#role_definitions>
+    Role {
+        Alert = "alert",
+        AlertDialog = "alertdialog",
+        Application = "application",
+        Article = "article",
+        Banner = "banner",
+        Button = "button",
+        Cell = "cell",
+        Checkbox = "checkbox",
+        ColumnHeader = "columnheader",
+        Combobox = "combobox",
+        Complementary = "complementary",
+        ContentInfo = "contentinfo",
+        Dialog = "dialog",
+        Document = "document",
+        Feed = "feed",
+        Figure = "figure",
+        Form = "form",
+        Grid = "grid",
+        GridCell = "gridcell",
+        Group = "group",
+        Heading = "heading",
+        Img = "img",
+        Link = "link",
+        List = "list",
+        Listbox = "listbox",
+        ListItem = "listitem",
+        Log = "log",
+        Main = "main",
+        Marquee = "marquee",
+        Math = "math",
+        Menu = "menu",
+        Menubar = "menubar",
+        MenuItem = "menuitem",
+        MenuItemCheckbox = "menuitemcheckbox",
+        MenuItemRadio = "menuitemradio",
+        Meter = "meter",
+        Navigation = "navigation",
+        None = "none",
+        Note = "note",
+        Option = "option",
+        Presentation = "presentation",
+        ProgressBar = "progressbar",
+        Radio = "radio",
+        RadioGroup = "radiogroup",
+        Region = "region",
+        Row = "row",
+        RowGroup = "rowgroup",
+        RowHeader = "rowheader",
+        Scrollbar = "scrollbar",
+        Search = "search",
+        Searchbox = "searchbox",
+        Separator = "separator",
+        Slider = "slider",
+        SpinButton = "spinbutton",
+        Status = "status",
+        Switch = "switch",
+        Tab = "tab",
+        Table = "table",
+        TabList = "tablist",
+        TabPanel = "tabpanel",
+        Term = "term",
+        Textbox = "textbox",
+        Timer = "timer",
+        Toolbar = "toolbar",
+        Tooltip = "tooltip",
+        Tree = "tree",
+        TreeGrid = "treegrid",
+        TreeItem = "treeitem",
+    }
+
+    /// Value of `aria-checked` and `aria-pressed` attributes
+    Tristate {
+        True = "true",
+        False = "false",
+        Mixed = "mixed",
+    }
+
+    /// Value of `aria-live` attribute
+    Live {
+        Off = "off",
+        Polite = "polite",
+        Assertive = "assertive",
+    }
+}
+
+impl From<bool> for Tristate {
+    fn from(val: bool) -> Self {
+        match val {
+            true => Tristate::True,
+            false => Tristate::False,
+        }
+    }
+}
+
+/// Typed `role` and `aria-*` attributes
+///
+/// This is implemented for every type that implements `AttributeApi`.
+pub trait AriaApi: AttributeApi {
+    fn role(self, val: Role) -> Self {
+        self.set_attr("role", val)
+    }
+
+    fn aria_checked(self, val: impl Into<Tristate>) -> Self {
+        self.set_attr("aria-checked", val.into())
+    }
+
+    fn aria_pressed(self, val: impl Into<Tristate>) -> Self {
+        self.set_attr("aria-pressed", val.into())
+    }
+
+    fn aria_disabled(self, val: bool) -> Self {
+        self.set_attr("aria-disabled", val)
+    }
+
+    fn aria_hidden(self, val: bool) -> Self {
+        self.set_attr("aria-hidden", val)
+    }
+
+    fn aria_valuenow(self, val: impl Into<f64>) -> Self {
+        self.set_attr("aria-valuenow", val.into())
+    }
+
+    fn aria_valuemin(self, val: impl Into<f64>) -> Self {
+        self.set_attr("aria-valuemin", val.into())
+    }
+
+    fn aria_valuemax(self, val: impl Into<f64>) -> Self {
+        self.set_attr("aria-valuemax", val.into())
+    }
+
+    /// Human readable text of the current value, e.g. `40%`
+    fn aria_valuetext(self, val: impl ToString) -> Self {
+        self.set_attr("aria-valuetext", val)
+    }
+
+    fn aria_label(self, val: impl ToString) -> Self {
+        self.set_attr("aria-label", val)
+    }
+
+    fn try_aria_label(self, val: Option<impl ToString>) -> Self {
+        match val {
+            Some(val) => self.aria_label(val),
+            None => self,
+        }
+    }
+
+    /// Ids of the elements that label this element, separated by spaces
+    fn aria_labelledby(self, val: impl ToString) -> Self {
+        self.set_attr("aria-labelledby", val)
+    }
+
+    /// Ids of the elements that describe this element, separated by spaces
+    fn aria_describedby(self, val: impl ToString) -> Self {
+        self.set_attr("aria-describedby", val)
+    }
+
+    fn aria_live(self, val: Live) -> Self {
+        self.set_attr("aria-live", val)
+    }
+}
+
+impl<T: AttributeApi> AriaApi for T {}
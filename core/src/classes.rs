@@ -0,0 +1,168 @@
+//! Builder for the `class` attribute.
+//!
+//! [`Classes`] collects classes in order without duplicates, which makes adding
+//! classes conditionally easy:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! let focused = true;
+//! let disabled = false;
+//! let button: Node<()> = html::button()
+//!     .class("primary")
+//!     .classes(
+//!         classes()
+//!             .add("button")
+//!             .add_if(focused, "focused")
+//!             .add_if(disabled, "disabled"),
+//!     );
+//!
+//! assert_eq!(button.get_class(), Some("primary button focused"));
+//! ```
+//!
+//! `classes` merges the classes with the existing ones, while `class` replaces
+//! them. Single classes can be changed using `add_class`, `remove_class` and
+//! `toggle_class`:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! let node: Node<()> = html::div()
+//!     .class("card active")
+//!     .toggle_class("active", false)
+//!     .toggle_class("selected", true)
+//!     .remove_class("card");
+//!
+//! assert_eq!(node.get_class(), Some("selected"));
+//! assert!(node.has_class("selected"));
+//! assert!(!node.remove_class("selected").has_attr("class"));
+//! ```
+//!
+//! Libraries can use [`Classes::with_prefix`] to avoid collisions with classes
+//! used by the host page.
+
+use std::fmt;
+
+/// Create an empty [`Classes`] builder
+pub fn classes() -> Classes {
+    Classes::new()
+}
+
+/// Ordered set of classes
+///
+/// See [module docs](crate::classes) for more details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Classes {
+    prefix: Option<String>,
+    classes: Vec<String>,
+}
+
+impl Classes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create builder that adds `prefix` to every class added to it
+    ///
+    /// ```rust
+    /// # use savory::prelude::*;
+    /// let classes = Classes::with_prefix("sv-").add("button").add("ghost");
+    /// assert_eq!(classes.to_string(), "sv-button sv-ghost");
+    /// ```
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            classes: vec![],
+        }
+    }
+
+    fn prefixed(&self, class: &str) -> String {
+        match self.prefix {
+            Some(ref prefix) => format!("{}{}", prefix, class),
+            None => class.to_string(),
+        }
+    }
+
+    /// Add class, or multiple classes separated by whitespace
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, class: impl AsRef<str>) -> Self {
+        for class in class.as_ref().split_whitespace() {
+            let class = self.prefixed(class);
+            if !self.classes.contains(&class) {
+                self.classes.push(class);
+            }
+        }
+        self
+    }
+
+    pub fn add_if(self, condition: bool, class: impl AsRef<str>) -> Self {
+        match condition {
+            true => self.add(class),
+            false => self,
+        }
+    }
+
+    pub fn try_add(self, class: Option<impl AsRef<str>>) -> Self {
+        match class {
+            Some(class) => self.add(class),
+            None => self,
+        }
+    }
+
+    /// Remove class, the prefix is added to `class` before removing it
+    pub fn remove(mut self, class: &str) -> Self {
+        let class = self.prefixed(class);
+        self.classes.retain(|c| *c != class);
+        self
+    }
+
+    /// Check if `class` was added, the prefix is added to `class` before
+    /// looking it up
+    ///
+    /// ```rust
+    /// # use savory::prelude::*;
+    /// let classes = Classes::with_prefix("sv-").add("button");
+    /// assert!(classes.contains("button"));
+    /// assert!(!classes.remove("button").contains("button"));
+    /// ```
+    pub fn contains(&self, class: &str) -> bool {
+        let class = self.prefixed(class);
+        self.classes.contains(&class)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.classes.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+}
+
+impl fmt::Display for Classes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.classes.join(" "))
+    }
+}
+
+impl From<&str> for Classes {
+    fn from(source: &str) -> Self {
+        Classes::new().add(source)
+    }
+}
+
+impl From<String> for Classes {
+    fn from(source: String) -> Self {
+        Classes::new().add(source)
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for Classes {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for class in iter {
+            *self = std::mem::take(self).add(class);
+        }
+    }
+}
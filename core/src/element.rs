@@ -11,11 +11,59 @@
 //! App element are normal elements but have one main difference, the `Config`
 //! type is `Url` type, so the app can do routing logic on the initialization.
 //!
+//! ## Lifecycle
+//!
+//! Elements can receive messages on lifecycle events (see [`Lifecycle`]) by
+//! implementing [`Element::lifecycle`], these messages are only sent to
+//! elements that are created using [`ElementExt::mount`] and removed using
+//! [`ElementExt::unmount`].
+//!
+//! ```rust
+//! use savory::prelude::*;
+//!
+//! struct Chart {
+//!     canvas: ElRef<web_sys::HtmlCanvasElement>,
+//! }
+//!
+//! enum Msg {
+//!     Draw,
+//!     Stop,
+//! }
+//!
+//! impl Element for Chart {
+//!     type Message = Msg;
+//!     type Config = ();
+//!
+//!     fn init(_: (), _: &mut impl Orders<Msg>, _: Env) -> Self {
+//!         Chart { canvas: ElRef::default() }
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, _: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             // the canvas is rendered and we can draw on it
+//!             Msg::Draw => { /* .. */ }
+//!             // clean up before the element is dropped
+//!             Msg::Stop => { /* .. */ }
+//!         }
+//!     }
+//!
+//!     fn lifecycle(event: Lifecycle) -> Option<Msg> {
+//!         match event {
+//!             Lifecycle::Mounted | Lifecycle::EnvChanged => Some(Msg::Draw),
+//!             Lifecycle::BeforeUnmount => Some(Msg::Stop),
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! # TODO Examples
 //! # TODO Helper types
 
 use crate::prelude::*;
-use seed::prelude::{Node, Orders, Url};
+use seed::{
+    futures::StreamExt,
+    prelude::{Node, Url},
+};
 
 /// Trait used to create element and handle element messages and update element
 /// state accordingly.
@@ -45,8 +93,59 @@ pub trait Element {
     /// update method that recive `Self::Message` and update the model state
     /// accordingly.
     fn update(&mut self, _: Self::Message, _: &mut impl Orders<Self::Message>);
+
+    /// Message sent to the element on lifecycle `event`, returns `None` if the
+    /// element isn't interested in the event (the default).
+    ///
+    /// See [`Lifecycle`] for the available events.
+    fn lifecycle(_event: Lifecycle) -> Option<Self::Message> {
+        None
+    }
+}
+
+/// Lifecycle events of elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    /// Element is rendered for the first time, `ElRef`s used in the first
+    /// view are available at this point
+    Mounted,
+    /// Element is about to be dropped from its parent, this is the last
+    /// chance to clean up (e.g. drop stream handles or listeners)
+    BeforeUnmount,
+    /// A value in the `Env` the element was initialized with have changed
+    EnvChanged,
+}
+
+/// Extension trait for `Element` used by parents to create and remove child
+/// elements while sending lifecycle messages to them
+pub trait ElementExt: Element + Sized {
+    /// Same as `init` but also schedule `Lifecycle::Mounted` message after the
+    /// next render and send `Lifecycle::EnvChanged` message when `env`
+    /// changes
+    fn mount(config: Self::Config, orders: &mut impl Orders<Self::Message>, env: Env) -> Self {
+        if let Some(msg) = Self::lifecycle(Lifecycle::Mounted) {
+            orders.after_next_render(move |_| msg);
+        }
+        if Self::lifecycle(Lifecycle::EnvChanged).is_some() {
+            orders.stream(
+                env.all_changes()
+                    .map(|_| Self::lifecycle(Lifecycle::EnvChanged)),
+            );
+        }
+        Self::init(config, orders, env)
+    }
+
+    /// Send `Lifecycle::BeforeUnmount` message to the element, parents should
+    /// call this right before dropping the element
+    fn unmount(&mut self, orders: &mut impl Orders<Self::Message>) {
+        if let Some(msg) = Self::lifecycle(Lifecycle::BeforeUnmount) {
+            self.update(msg, orders);
+        }
+    }
 }
 
+impl<T: Element> ElementExt for T {}
+
 /// Extension trait for `Element` when it's used on App element
 ///
 /// This trait provides functions that mounts the app element on HTML node by
@@ -102,7 +201,7 @@ where
     fn start_at(id: &str) -> seed::app::App<Self::Message, Self, Node<Self::Message>> {
         seed::app::App::start(
             id,
-            |url, orders| Self::init(url, orders, Env::base_branch()),
+            |url, orders| Self::mount(url, orders, Env::base_branch()),
             |msg, app, orders| app.update(msg, orders),
             |app| app.view(),
         )
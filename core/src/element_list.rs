@@ -0,0 +1,354 @@
+//! Keyed collection of child elements.
+//!
+//! [`ElementList`] owns a dynamic list of child elements, each child is
+//! identified by a key that is used to route messages to it and to render it
+//! with stable `el_key`, so Seed's diffing keeps the DOM state of children
+//! (e.g. focus and input values) when the list changes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use savory::prelude::*;
+//!
+//! struct Todo(String);
+//!
+//! impl Element for Todo {
+//!     type Config = String;
+//!     type Message = ();
+//!
+//!     fn init(text: String, _: &mut impl Orders<()>, _: Env) -> Self {
+//!         Todo(text)
+//!     }
+//!
+//!     fn update(&mut self, _: (), _: &mut impl Orders<()>) {}
+//! }
+//!
+//! impl View<Node<()>> for Todo {
+//!     fn view(&self) -> Node<()> {
+//!         html::li().push(self.0.clone())
+//!     }
+//! }
+//!
+//! struct TodoList {
+//!     next_id: u32,
+//!     todos: ElementList<u32, Todo>,
+//! }
+//!
+//! enum Msg {
+//!     Add(String),
+//!     Remove(u32),
+//!     Todos(element_list::Msg<u32, Todo>),
+//! }
+//!
+//! impl Element for TodoList {
+//!     type Config = ();
+//!     type Message = Msg;
+//!
+//!     fn init(_: (), orders: &mut impl Orders<Msg>, env: Env) -> Self {
+//!         TodoList {
+//!             next_id: 0,
+//!             todos: ElementList::mount(vec![], &mut orders.proxy(Msg::Todos), env),
+//!         }
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             Msg::Add(text) => {
+//!                 self.next_id += 1;
+//!                 self.todos
+//!                     .insert(self.next_id, text, &mut orders.proxy(Msg::Todos));
+//!             }
+//!             Msg::Remove(id) => {
+//!                 self.todos.remove(&id, &mut orders.proxy(Msg::Todos));
+//!             }
+//!             Msg::Todos(msg) => self.todos.update(msg, &mut orders.proxy(Msg::Todos)),
+//!         }
+//!     }
+//! }
+//!
+//! impl View<Node<Msg>> for TodoList {
+//!     fn view(&self) -> Node<Msg> {
+//!         html::ul().push(self.todos.view().map_msg(Msg::Todos))
+//!     }
+//! }
+//! ```
+
+use crate::prelude::*;
+use seed::virtual_dom::el_key;
+
+/// Messages handled by [`ElementList`]
+pub enum Msg<K, E: Element> {
+    /// Message for the child with the key `K`, messages for keys that are
+    /// removed are ignored
+    Child(K, E::Message),
+    /// Initialize new child using the config and insert it with the key `K`,
+    /// if the key already exists its child will be replaced
+    Insert(K, E::Config),
+    /// Remove the child with the key `K`
+    Remove(K),
+    /// Remove all children
+    Clear,
+}
+
+/// Keyed collection of child elements
+///
+/// Children are kept in insertion order, each child is initialized with its
+/// own branch of the list `Env`.
+///
+/// See [module docs](crate::element_list) for an example.
+pub struct ElementList<K, E> {
+    env: Env,
+    children: Vec<(K, E)>,
+}
+
+impl<K, E> Element for ElementList<K, E>
+where
+    K: Clone + PartialEq + ToString + 'static,
+    E: Element + 'static,
+{
+    type Message = Msg<K, E>;
+    type Config = Vec<(K, E::Config)>;
+
+    fn init(config: Self::Config, orders: &mut impl Orders<Msg<K, E>>, env: Env) -> Self {
+        let mut list = ElementList {
+            env,
+            children: vec![],
+        };
+        for (key, config) in config {
+            list.insert(key, config, orders);
+        }
+        list
+    }
+
+    fn update(&mut self, msg: Msg<K, E>, orders: &mut impl Orders<Msg<K, E>>) {
+        match msg {
+            Msg::Child(key, msg) => {
+                if let Some(child) = self.get_mut(&key) {
+                    child.update(msg, &mut orders.proxy(child_msg(key)));
+                }
+            }
+            Msg::Insert(key, config) => self.insert(key, config, orders),
+            Msg::Remove(key) => {
+                self.remove(&key, orders);
+            }
+            Msg::Clear => self.clear(orders),
+        }
+    }
+}
+
+impl<K, E> ElementList<K, E>
+where
+    K: Clone + PartialEq + ToString + 'static,
+    E: Element + 'static,
+{
+    /// Mount new child using `config` and insert it with `key`
+    ///
+    /// If `key` already exists its child will be unmounted and replaced, the
+    /// new child keeps the same position in the list.
+    pub fn insert(&mut self, key: K, config: E::Config, orders: &mut impl Orders<Msg<K, E>>) {
+        let child = E::mount(
+            config,
+            &mut orders.proxy(child_msg(key.clone())),
+            self.env.branch(),
+        );
+        match self.children.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, old)) => {
+                unmount(key, old, orders);
+                *old = child;
+            }
+            None => self.children.push((key, child)),
+        }
+    }
+
+    /// Unmount the child with `key`, remove it and return it
+    pub fn remove(&mut self, key: &K, orders: &mut impl Orders<Msg<K, E>>) -> Option<E> {
+        let index = self.children.iter().position(|(k, _)| k == key)?;
+        let (key, mut child) = self.children.remove(index);
+        unmount(key, &mut child, orders);
+        Some(child)
+    }
+
+    /// Unmount and remove all children
+    pub fn clear(&mut self, orders: &mut impl Orders<Msg<K, E>>) {
+        for (key, mut child) in self.children.drain(..) {
+            unmount(key, &mut child, orders);
+        }
+    }
+}
+
+impl<K: PartialEq, E> ElementList<K, E> {
+    pub fn get(&self, key: &K) -> Option<&E> {
+        self.children
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, child)| child)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut E> {
+        self.children
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, child)| child)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.children.iter().any(|(k, _)| k == key)
+    }
+
+    /// Keys of the children in order
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.children.iter().map(|(key, _)| key)
+    }
+
+    /// Children with their keys in order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &E)> {
+        self.children.iter().map(|(key, child)| (key, child))
+    }
+
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<K, E> View<Vec<Node<Msg<K, E>>>> for ElementList<K, E>
+where
+    K: Clone + ToString + 'static,
+    E: Element + View<Node<<E as Element>::Message>> + 'static,
+{
+    fn view(&self) -> Vec<Node<Msg<K, E>>> {
+        self.children
+            .iter()
+            .map(|(key, child)| {
+                let mut node = child.view().map_msg(child_msg(key.clone()));
+                if let Node::Element(ref mut el) = node {
+                    el.key = Some(el_key(key));
+                }
+                node
+            })
+            .collect()
+    }
+}
+
+fn unmount<K, E>(key: K, child: &mut E, orders: &mut impl Orders<Msg<K, E>>)
+where
+    K: Clone + 'static,
+    E: Element + 'static,
+{
+    // avoid creating orders proxy for children that don't handle unmounting
+    if E::lifecycle(Lifecycle::BeforeUnmount).is_some() {
+        child.unmount(&mut orders.proxy(child_msg(key)));
+    }
+}
+
+fn child_msg<K: Clone, E: Element>(key: K) -> impl FnOnce(E::Message) -> Msg<K, E> + Clone {
+    move |msg| Msg::Child(key, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{Harness, TestOrders};
+
+    struct Item(&'static str);
+
+    impl Element for Item {
+        type Config = &'static str;
+        type Message = ();
+
+        fn init(text: &'static str, _: &mut impl Orders<()>, _: Env) -> Self {
+            Item(text)
+        }
+
+        fn update(&mut self, _: (), _: &mut impl Orders<()>) {}
+    }
+
+    impl View<Node<()>> for Item {
+        fn view(&self) -> Node<()> {
+            html::li().push(self.0)
+        }
+    }
+
+    #[test]
+    fn test_view_keys() {
+        let mut list = ElementList {
+            env: Env::base_branch(),
+            children: vec![(1, Item("one")), (2, Item("two")), (3, Item("three"))],
+        };
+        let mut orders = TestOrders::default();
+        assert_eq!(list.remove(&2, &mut orders).map(|item| item.0), Some("two"));
+        assert_eq!(list.remove(&2, &mut orders).map(|item| item.0), None);
+        assert_eq!(list.keys().copied().collect::<Vec<_>>(), vec![1, 3]);
+
+        let keys = list
+            .view()
+            .iter()
+            .map(|node| node.el_key().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![Some(el_key(&1)), Some(el_key(&3))]);
+    }
+
+    struct Counter {
+        count: u32,
+        mounted: bool,
+    }
+
+    enum CounterMsg {
+        Increase,
+        IncreaseLater,
+        Mounted,
+    }
+
+    impl Element for Counter {
+        type Config = u32;
+        type Message = CounterMsg;
+
+        fn init(count: u32, _: &mut impl Orders<CounterMsg>, _: Env) -> Self {
+            Counter {
+                count,
+                mounted: false,
+            }
+        }
+
+        fn update(&mut self, msg: CounterMsg, orders: &mut impl Orders<CounterMsg>) {
+            match msg {
+                CounterMsg::Increase => self.count += 1,
+                CounterMsg::IncreaseLater => {
+                    orders.send_after(100, || CounterMsg::Increase);
+                }
+                CounterMsg::Mounted => self.mounted = true,
+            }
+        }
+
+        fn lifecycle(event: Lifecycle) -> Option<CounterMsg> {
+            match event {
+                Lifecycle::Mounted => Some(CounterMsg::Mounted),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_harness_children() {
+        let mut list = Harness::<ElementList<u32, Counter>>::new(vec![(1, 0), (2, 5)]);
+        list.render();
+        assert!(list.element().iter().all(|(_, counter)| counter.mounted));
+
+        list.send(Msg::Child(1, CounterMsg::IncreaseLater));
+        assert_eq!(list.element().get(&1).map(|c| c.count), Some(0));
+        list.advance(100);
+        assert_eq!(list.element().get(&1).map(|c| c.count), Some(1));
+
+        list.send(Msg::Insert(3, 7)).send(Msg::Remove(2));
+        assert_eq!(
+            list.element().keys().copied().collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(!list.element().get(&3).unwrap().mounted);
+        list.render();
+        assert!(list.element().get(&3).unwrap().mounted);
+    }
+}
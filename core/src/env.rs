@@ -1,12 +1,176 @@
-use std::{cell::RefCell, rc::Rc};
-use type_map::TypeMap;
+//! Environment shared between elements.
+//!
+//! `Env` is a tree of branches, each branch holds values that are accessible
+//! in the branch and its descendants branches. Values are stored by their
+//! type, or by [`EnvKey`] when we need more than one value of the same type.
+//!
+//! ```rust
+//! use savory::{env::EnvKey, prelude::*};
+//!
+//! const TITLE: EnvKey<String> = EnvKey::new("title");
+//! const SUBTITLE: EnvKey<String> = EnvKey::new("subtitle");
+//!
+//! let env = Env::base_branch()
+//!     .insert(42u32)
+//!     .insert_key(TITLE, "Savory".to_string())
+//!     .insert_key(SUBTITLE, "Rust UI".to_string());
+//!
+//! let branch = env.branch().insert_key(TITLE, "Docs".to_string());
+//! assert_eq!(branch.get::<u32>(), Some(42));
+//! assert_eq!(branch.get_key(TITLE), Some("Docs".to_string()));
+//! assert_eq!(*branch.get_ref_key(SUBTITLE).unwrap(), "Rust UI");
+//!
+//! assert_eq!(branch.remove_key(TITLE), Some("Docs".to_string()));
+//! assert_eq!(branch.get_key(TITLE), Some("Savory".to_string()));
+//! ```
 
-type Branch = Rc<RefCell<TypeMap>>;
+use seed::futures::{channel::mpsc, Stream};
+use std::{
+    any::{type_name, Any, TypeId},
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    rc::{Rc, Weak},
+};
+
+/// Key used to store more than one value of the same type in [`Env`]
+///
+/// Keys are identified by their type and name, so two keys of the same type
+/// must have different names.
+///
+/// ```rust
+/// use savory::env::EnvKey;
+///
+/// const PRIMARY_COLOR: EnvKey<&str> = EnvKey::new("primary-color");
+/// const SECONDARY_COLOR: EnvKey<&str> = EnvKey::new("secondary-color");
+/// ```
+pub struct EnvKey<T> {
+    name: &'static str,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> EnvKey<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for EnvKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EnvKey<T> {}
+
+impl<T> fmt::Debug for EnvKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EnvKey<{}>({:?})", type_name::<T>(), self.name)
+    }
+}
+
+/// Where the value is stored, values stored by type have no key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Slot {
+    type_id: TypeId,
+    key: Option<&'static str>,
+}
+
+impl Slot {
+    fn of<T: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            key: None,
+        }
+    }
+
+    fn key<T: 'static>(key: EnvKey<T>) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            key: Some(key.name),
+        }
+    }
+}
+
+struct Value {
+    type_name: &'static str,
+    val: Box<dyn Any>,
+}
+
+type Values = HashMap<Slot, Value>;
+
+type Branch = Rc<RefCell<Values>>;
+
+/// Change listeners shared by all branches of the environment tree
+type Listeners = Rc<RefCell<Vec<Listener>>>;
+
+/// Sender used by change listeners
+trait Sender {
+    /// Send the new value, returns `false` if nobody is listening anymore
+    fn send(&self, val: &dyn Any) -> bool;
+
+    /// Returns `true` if nobody is listening anymore
+    fn is_closed(&self) -> bool;
+}
+
+impl<T: Clone + 'static> Sender for mpsc::UnboundedSender<T> {
+    fn send(&self, val: &dyn Any) -> bool {
+        let val = val
+            .downcast_ref::<T>()
+            .expect("listeners are matched by slot")
+            .clone();
+        self.unbounded_send(val).is_ok()
+    }
+
+    fn is_closed(&self) -> bool {
+        mpsc::UnboundedSender::is_closed(self)
+    }
+}
+
+struct Listener {
+    /// Slot the listener listens to, or `None` to listen to all slots
+    slot: Option<Slot>,
+    /// Listener branch followed by its predecessors branches
+    branches: Vec<Weak<RefCell<Values>>>,
+    send: Box<dyn Sender>,
+}
+
+/// Value visible in an environment branch, returned by [`Env::entries`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvEntry {
+    /// Value type name as returned by `std::any::type_name`
+    pub type_name: &'static str,
+    /// Key name if the value is stored using [`EnvKey`]
+    pub key: Option<&'static str>,
+    pub origin: Origin,
+}
+
+/// Where the value of [`EnvEntry`] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    /// Value is stored in this branch only
+    Local,
+    /// Value is stored in this branch and shadows a value stored in a
+    /// predecessor branch
+    Shadowing,
+    /// Value is inherited from a predecessor branch, `depth` is the distance
+    /// to that branch (1 for the parent branch)
+    Inherited { depth: usize },
+}
 
 #[derive(Clone)]
 pub struct Env {
     branch: Branch,
     parent: Option<Box<Env>>,
+    listeners: Listeners,
 }
 
 impl Env {
@@ -14,13 +178,16 @@ impl Env {
         Env {
             branch: Rc::default(),
             parent: None,
+            listeners: Rc::default(),
         }
     }
 
     pub fn branch(&self) -> Self {
-        let mut res = Self::base_branch();
-        res.parent = Some(Box::new(self.clone()));
-        res
+        Env {
+            branch: Rc::default(),
+            parent: Some(Box::new(self.clone())),
+            listeners: Rc::clone(&self.listeners),
+        }
     }
 
     pub fn share(&self) -> Self {
@@ -28,15 +195,20 @@ impl Env {
     }
 
     pub fn get<T: Clone + 'static>(&self) -> Option<T> {
-        self.branch
-            .borrow()
-            .get::<T>()
-            .cloned()
-            .or_else(|| self.parent.as_ref().and_then(|env| env.get::<T>()))
+        self.get_ref::<T>().map(|val| val.clone())
+    }
+
+    /// Same as `get` but borrow the value instead of cloning it
+    ///
+    /// # Panics
+    ///
+    /// Changing the value in its branch while it's borrowed will panic.
+    pub fn get_ref<T: 'static>(&self) -> Option<Ref<'_, T>> {
+        self.get_slot(Slot::of::<T>())
     }
 
     pub fn contains<T: 'static>(&self) -> bool {
-        self.branch.borrow().contains::<T>()
+        self.branch.borrow().contains_key(&Slot::of::<T>())
     }
 
     /// Insert a value in to the environment.
@@ -51,7 +223,7 @@ impl Env {
     /// will only shadow them!. In case this value type already exists in the
     /// current environment it will be replaced.
     pub fn insert<T: 'static>(self, val: T) -> Self {
-        self.branch.borrow_mut().insert(val);
+        self.insert_slot(Slot::of::<T>(), val);
         self
     }
 
@@ -61,7 +233,7 @@ impl Env {
     /// already exists.
     pub fn try_insert<T: 'static>(self, val: T) -> Self {
         if !self.contains::<T>() {
-            self.branch.borrow_mut().insert(val);
+            self.insert_slot(Slot::of::<T>(), val);
         }
         self
     }
@@ -73,29 +245,272 @@ impl Env {
     /// this method will panic if the passed type value is not initialized, you
     /// can use `try_update` for non-panicing version.
     pub fn update<T: 'static, F: FnOnce(T) -> T>(self, f: F) -> Self {
-        // update current environment
-        let val = { self.branch.borrow_mut().remove::<T>() };
-        if let Some(val) = val {
-            self.branch.borrow_mut().insert(f(val));
+        if self.update_slot(Slot::of::<T>(), f) {
             return self;
         }
 
         // panic if type value doesn't exists
         panic!(
             "Env::update(..) failed, type {} doesn't exists in current environment branch",
-            std::any::type_name::<T>()
+            type_name::<T>()
         )
     }
 
     pub fn try_update<T: 'static, F: FnOnce(T) -> T>(self, f: F) -> Self {
-        // update current environment
-        let val = { self.branch.borrow_mut().remove::<T>() };
-        if let Some(val) = val {
-            self.branch.borrow_mut().insert(f(val));
+        self.update_slot(Slot::of::<T>(), f);
+        self
+    }
+
+    /// Remove value from the current environment branch and return it
+    ///
+    /// Values in predecessors branches are not affected, if the removed value
+    /// was shadowing one of them, it will be accessible again in this branch.
+    pub fn remove<T: 'static>(&self) -> Option<T> {
+        self.remove_slot(Slot::of::<T>())
+    }
+
+    /// Stream of `T` values, it yields the new value every time `T` changes
+    /// in this environment.
+    ///
+    /// Changes are made by `insert`, `try_insert`, `update`, `try_update` and
+    /// `remove` on this branch or one of its predecessors branches, changes
+    /// made on predecessors branches are not yielded if this branch (or a
+    /// branch in between) shadow `T`.
+    ///
+    /// The stream ends once all clones of this branch are dropped. Elements
+    /// usually use [`OrdersExt::subscribe_env`] instead of this method.
+    ///
+    /// [`OrdersExt::subscribe_env`]: crate::prelude::OrdersExt::subscribe_env
+    pub fn changes<T: Clone + 'static>(&self) -> impl Stream<Item = T> {
+        self.slot_changes(Slot::of::<T>())
+    }
+
+    /// Stream that yields every time a value accessible in this branch changes
+    ///
+    /// This is the same as `changes` but for all values, including values
+    /// stored using keys and removed values.
+    pub fn all_changes(&self) -> impl Stream<Item = ()> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.listen(None, Box::new(sender));
+        receiver
+    }
+
+    /// Same as `get` but for values stored using `key`
+    pub fn get_key<T: Clone + 'static>(&self, key: EnvKey<T>) -> Option<T> {
+        self.get_ref_key(key).map(|val| val.clone())
+    }
+
+    /// Same as `get_ref` but for values stored using `key`
+    pub fn get_ref_key<T: 'static>(&self, key: EnvKey<T>) -> Option<Ref<'_, T>> {
+        self.get_slot(Slot::key(key))
+    }
+
+    /// Same as `contains` but for values stored using `key`
+    pub fn contains_key<T: 'static>(&self, key: EnvKey<T>) -> bool {
+        self.branch.borrow().contains_key(&Slot::key(key))
+    }
+
+    /// Same as `insert` but store the value using `key`, values stored using
+    /// different keys don't shadow each other
+    pub fn insert_key<T: 'static>(self, key: EnvKey<T>, val: T) -> Self {
+        self.insert_slot(Slot::key(key), val);
+        self
+    }
+
+    /// Same as `try_insert` but for values stored using `key`
+    pub fn try_insert_key<T: 'static>(self, key: EnvKey<T>, val: T) -> Self {
+        if !self.contains_key(key) {
+            self.insert_slot(Slot::key(key), val);
+        }
+        self
+    }
+
+    /// Same as `update` but for values stored using `key`
+    ///
+    /// # panic
+    ///
+    /// this method will panic if there is no value stored using `key` in the
+    /// current environment branch, you can use `try_update_key` for
+    /// non-panicing version.
+    pub fn update_key<T: 'static, F: FnOnce(T) -> T>(self, key: EnvKey<T>, f: F) -> Self {
+        if self.update_slot(Slot::key(key), f) {
             return self;
         }
+
+        panic!(
+            "Env::update_key(..) failed, key {:?} doesn't exists in current environment branch",
+            key
+        )
+    }
+
+    /// Same as `try_update` but for values stored using `key`
+    pub fn try_update_key<T: 'static, F: FnOnce(T) -> T>(self, key: EnvKey<T>, f: F) -> Self {
+        self.update_slot(Slot::key(key), f);
         self
     }
+
+    /// Same as `remove` but for values stored using `key`
+    pub fn remove_key<T: 'static>(&self, key: EnvKey<T>) -> Option<T> {
+        self.remove_slot(Slot::key(key))
+    }
+
+    /// Same as `changes` but for values stored using `key`
+    pub fn changes_key<T: Clone + 'static>(&self, key: EnvKey<T>) -> impl Stream<Item = T> {
+        self.slot_changes(Slot::key(key))
+    }
+
+    /// Values accessible in this branch and where they come from, this is
+    /// useful for debugging.
+    ///
+    /// Entries are sorted by their type name and key.
+    ///
+    /// ```rust
+    /// use savory::{env::{EnvEntry, Origin}, prelude::*};
+    ///
+    /// let base = Env::base_branch().insert(1u8).insert(true);
+    /// let branch = base.branch().insert(false).insert(1i8);
+    ///
+    /// let entry = |type_name, origin| EnvEntry { type_name, key: None, origin };
+    /// assert_eq!(
+    ///     branch.entries(),
+    ///     vec![
+    ///         entry("bool", Origin::Shadowing),
+    ///         entry("i8", Origin::Local),
+    ///         entry("u8", Origin::Inherited { depth: 1 }),
+    ///     ]
+    /// );
+    /// ```
+    pub fn entries(&self) -> Vec<EnvEntry> {
+        let mut entries: Vec<(Slot, EnvEntry)> = vec![];
+        let mut env = Some(self);
+        let mut depth = 0;
+        while let Some(current) = env {
+            for (slot, value) in current.branch.borrow().iter() {
+                match entries.iter_mut().find(|(s, _)| s == slot) {
+                    Some((_, entry)) if entry.origin == Origin::Local => {
+                        entry.origin = Origin::Shadowing
+                    }
+                    Some(_) => {}
+                    None => entries.push((
+                        *slot,
+                        EnvEntry {
+                            type_name: value.type_name,
+                            key: slot.key,
+                            origin: match depth {
+                                0 => Origin::Local,
+                                depth => Origin::Inherited { depth },
+                            },
+                        },
+                    )),
+                }
+            }
+            env = current.parent.as_deref();
+            depth += 1;
+        }
+        let mut entries = entries
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| (entry.type_name, entry.key));
+        entries
+    }
+
+    fn get_slot<T: 'static>(&self, slot: Slot) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.branch.borrow(), |values| {
+            values.get(&slot).and_then(|value| value.val.downcast_ref())
+        })
+        .ok()
+        .or_else(|| self.parent.as_ref().and_then(|env| env.get_slot(slot)))
+    }
+
+    fn insert_slot<T: 'static>(&self, slot: Slot, val: T) {
+        let value = Value {
+            type_name: type_name::<T>(),
+            val: Box::new(val),
+        };
+        self.branch.borrow_mut().insert(slot, value);
+        self.notify::<T>(slot);
+    }
+
+    /// Update value in the current branch, returns `false` if it doesn't exist
+    fn update_slot<T: 'static, F: FnOnce(T) -> T>(&self, slot: Slot, f: F) -> bool {
+        let val = { self.branch.borrow_mut().remove(&slot) };
+        match val.map(|value| value.val.downcast::<T>()) {
+            Some(Ok(val)) => {
+                self.insert_slot(slot, f(*val));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn remove_slot<T: 'static>(&self, slot: Slot) -> Option<T> {
+        let val = { self.branch.borrow_mut().remove(&slot) };
+        let val = val.and_then(|value| value.val.downcast::<T>().ok())?;
+        self.notify::<T>(slot);
+        Some(*val)
+    }
+
+    fn slot_changes<T: Clone + 'static>(&self, slot: Slot) -> impl Stream<Item = T> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.listen(Some(slot), Box::new(sender));
+        receiver
+    }
+
+    fn listen(&self, slot: Option<Slot>, send: Box<dyn Sender>) {
+        let mut branches = vec![Rc::downgrade(&self.branch)];
+        let mut parent = self.parent.as_ref();
+        while let Some(env) = parent {
+            branches.push(Rc::downgrade(&env.branch));
+            parent = env.parent.as_ref();
+        }
+        let mut listeners = self.listeners.borrow_mut();
+        // drop listeners whose streams are dropped, so they don't pile up when
+        // nothing changes
+        listeners.retain(|l| !l.send.is_closed());
+        listeners.push(Listener {
+            slot,
+            branches,
+            send,
+        });
+    }
+
+    /// Send the value this branch sees after it changed to listeners that see
+    /// the same value
+    fn notify<T: 'static>(&self, slot: Slot) {
+        let val = self.get_slot::<T>(slot);
+        self.listeners.borrow_mut().retain(|listener| {
+            if listener.slot.is_some_and(|s| s != slot) {
+                return true;
+            }
+            // listener branch is dropped, so no one is listening
+            if listener.branches[0].strong_count() == 0 {
+                return false;
+            }
+            // listener sees this branch value if there is no branch in between
+            // that shadows it
+            for branch in listener.branches.iter().filter_map(Weak::upgrade) {
+                if Rc::ptr_eq(&branch, &self.branch) {
+                    return match (listener.slot, &val) {
+                        (None, _) => listener.send.send(&()),
+                        (Some(_), Some(val)) => listener.send.send(&**val),
+                        // value is removed, there is nothing to send
+                        (Some(_), None) => true,
+                    };
+                }
+                if branch.borrow().contains_key(&slot) {
+                    return true;
+                }
+            }
+            true
+        });
+    }
+}
+
+impl fmt::Debug for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries()).finish()
+    }
 }
 
 // FIXME: test all use cases for Env
@@ -135,4 +550,161 @@ mod tests {
         assert_eq!(base_2.get::<DarkTheme>(), Some(DarkTheme(false)));
         assert_eq!(base_2.get::<String>(), Some("Hi There".to_owned()));
     }
+
+    #[test]
+    fn test_changes() {
+        use seed::futures::{FutureExt, StreamExt};
+
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        struct DarkTheme(bool);
+
+        let base = Env::base_branch().insert(DarkTheme(false));
+        let child = base.branch();
+        let shadowing = base.branch().insert(DarkTheme(true));
+
+        let mut base_changes = base.changes::<DarkTheme>();
+        let mut child_changes = child.changes::<DarkTheme>();
+        let mut shadowing_changes = shadowing.changes::<DarkTheme>();
+        let mut strings = child.changes::<String>();
+
+        // changes in a branch reach descendants that don't shadow the value
+        let base = base.update(|_: DarkTheme| DarkTheme(true));
+        assert_eq!(
+            base_changes.next().now_or_never(),
+            Some(Some(DarkTheme(true)))
+        );
+        assert_eq!(
+            child_changes.next().now_or_never(),
+            Some(Some(DarkTheme(true)))
+        );
+        assert_eq!(shadowing_changes.next().now_or_never(), None);
+        assert_eq!(strings.next().now_or_never(), None);
+
+        // changes in a branch don't reach predecessors
+        let shadowing = shadowing.update(|_: DarkTheme| DarkTheme(false));
+        assert_eq!(
+            shadowing_changes.next().now_or_never(),
+            Some(Some(DarkTheme(false)))
+        );
+        assert_eq!(base_changes.next().now_or_never(), None);
+        assert_eq!(child_changes.next().now_or_never(), None);
+
+        // inserting a value shadows the inherited one
+        let child = child.insert(DarkTheme(false));
+        assert_eq!(
+            child_changes.next().now_or_never(),
+            Some(Some(DarkTheme(false)))
+        );
+        base.share().update(|_: DarkTheme| DarkTheme(false));
+        assert_eq!(child_changes.next().now_or_never(), None);
+
+        // changes end when the branch is dropped
+        drop(child);
+        base.share().update(|_: DarkTheme| DarkTheme(true));
+        assert_eq!(child_changes.next().now_or_never(), Some(None));
+        assert_eq!(
+            base_changes.next().now_or_never(),
+            Some(Some(DarkTheme(false)))
+        );
+        assert_eq!(
+            base_changes.next().now_or_never(),
+            Some(Some(DarkTheme(true)))
+        );
+        drop(shadowing);
+    }
+
+    #[test]
+    fn test_keys() {
+        const PRIMARY: EnvKey<&str> = EnvKey::new("primary");
+        const SECONDARY: EnvKey<&str> = EnvKey::new("secondary");
+
+        let base = Env::base_branch()
+            .insert("type")
+            .insert_key(PRIMARY, "blue")
+            .insert_key(SECONDARY, "gray");
+        let branch = base.branch().insert_key(PRIMARY, "red");
+
+        assert_eq!(branch.get::<&str>(), Some("type"));
+        assert_eq!(branch.get_key(PRIMARY), Some("red"));
+        assert_eq!(branch.get_key(SECONDARY), Some("gray"));
+        assert_eq!(base.get_key(PRIMARY), Some("blue"));
+        assert!(branch.contains_key(PRIMARY));
+        assert!(!branch.contains_key(SECONDARY));
+
+        let branch = branch.update_key(PRIMARY, |_| "green");
+        assert_eq!(*branch.get_ref_key(PRIMARY).unwrap(), "green");
+
+        let entry = |key, origin| EnvEntry {
+            type_name: "&str",
+            key,
+            origin,
+        };
+        assert_eq!(
+            branch.entries(),
+            vec![
+                entry(None, Origin::Inherited { depth: 1 }),
+                entry(Some("primary"), Origin::Shadowing),
+                entry(Some("secondary"), Origin::Inherited { depth: 1 }),
+            ]
+        );
+
+        assert_eq!(branch.remove_key(PRIMARY), Some("green"));
+        assert_eq!(branch.remove_key(PRIMARY), None);
+        assert_eq!(branch.get_key(PRIMARY), Some("blue"));
+        assert_eq!(base.remove::<&str>(), Some("type"));
+        assert_eq!(branch.get::<&str>(), None);
+    }
+
+    #[test]
+    fn test_remove_changes() {
+        use seed::futures::{FutureExt, StreamExt};
+
+        const PRIMARY: EnvKey<&str> = EnvKey::new("primary");
+
+        let base = Env::base_branch().insert_key(PRIMARY, "blue");
+        let branch = base.branch().insert_key(PRIMARY, "red");
+        let mut changes = branch.changes_key(PRIMARY);
+
+        // removing shadowing value yields the inherited value
+        branch.remove_key(PRIMARY);
+        assert_eq!(changes.next().now_or_never(), Some(Some("blue")));
+        base.remove_key(PRIMARY);
+        assert_eq!(changes.next().now_or_never(), None);
+    }
+
+    #[test]
+    fn test_all_changes() {
+        use seed::futures::{FutureExt, StreamExt};
+
+        let base = Env::base_branch().insert(1u8);
+        let branch = base.branch().insert(true);
+        let mut base_changes = base.all_changes();
+        let mut branch_changes = branch.all_changes();
+
+        let branch = branch.insert(1i8).update(|val: bool| !val);
+        assert_eq!(base_changes.next().now_or_never(), None);
+        assert_eq!(branch_changes.next().now_or_never(), Some(Some(())));
+        assert_eq!(branch_changes.next().now_or_never(), Some(Some(())));
+        assert_eq!(branch_changes.next().now_or_never(), None);
+
+        base.remove::<u8>();
+        assert_eq!(base_changes.next().now_or_never(), Some(Some(())));
+        assert_eq!(branch_changes.next().now_or_never(), Some(Some(())));
+
+        // shadowed values don't affect the branch
+        base.share().insert(false);
+        assert_eq!(base_changes.next().now_or_never(), Some(Some(())));
+        assert_eq!(branch_changes.next().now_or_never(), None);
+        drop(branch);
+    }
+
+    #[test]
+    fn test_dropped_listeners() {
+        let env = Env::base_branch().insert(1u8);
+        for _ in 0..10 {
+            drop(env.changes::<u8>());
+        }
+        let _changes = env.all_changes();
+        assert_eq!(env.listeners.borrow().len(), 1);
+    }
 }
@@ -1,50 +1,554 @@
 //! Traits and implementation that makes working `Node` events API declarative
 //! and convenient.
+//!
+//! Event handlers can return `Ms`, `Option<Ms>` or `()`, returning `None` or
+//! `()` means the event is ignored:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! enum Msg {
+//!     Submit,
+//! }
+//!
+//! let input: Node<Msg> = html::input()
+//!     .on_focus(|ev| ev.prevent_default())
+//!     .on_key_down(|ev| match ev.key().as_str() {
+//!         "Enter" => Some(Msg::Submit),
+//!         _ => None,
+//!     });
+//! ```
+//!
+//! Any other return type is a compile error:
+//!
+//! ```rust,compile_fail
+//! # use savory::prelude::*;
+//! enum Msg {
+//!     Submit,
+//! }
+//!
+//! let input: Node<Msg> = html::input().on_key_down(|_| "Submit");
+//! ```
+//!
+//! # Modifiers
+//!
+//! Every event method have `_with` variant that accepts [`Modifiers`], which
+//! change how the handler is called:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! enum Msg {
+//!     Submit,
+//!     Scroll(f64),
+//! }
+//!
+//! let form: Node<Msg> = html::form()
+//!     .on_submit_with(|_| Msg::Submit, Modifiers::prevent_default())
+//!     .push(html::button().push("Send"));
+//!
+//! let list: Node<Msg> = html::div().on_wheel_with(
+//!     |ev| Msg::Scroll(ev.delta_y()),
+//!     Modifiers::passive() | Modifiers::stop_propagation(),
+//! );
+//! ```
 
-use crate::prelude::*;
-use seed::prelude::{
-    ev, keyboard_ev, mouse_ev, pointer_ev, touch_ev, web_sys, El, EventHandler, JsCast, Node,
-};
-use std::any::{Any, TypeId};
+use crate::{html::HtmlEl, prelude::*};
+use paste::paste;
+use seed::prelude::{web_sys, At, AtValue, El, EventHandler, JsCast, Node};
+use std::{any::Any, ops::BitOr, rc::Rc};
 
+pub use keyboard::{shortcuts, Key, KeyCombo, KeyComboError};
 pub use seed::prelude::Ev;
 
+mod keyboard;
+
+/// Conversion from event handler output into optional message
+///
+/// This is implemented for `Ms`, `Option<Ms>` and `()`, the `Kind` parameter
+/// is one of the markers in [`handler_output`], it's inferred by the compiler
+/// and only exists to keep these implementations apart.
+///
+/// When `Ms` is `()` itself, both `Ms` and `()` implementations match handlers
+/// returning `()`, these handlers name the `Unit` marker (or return
+/// `Some(())`), and the message `()` is sent either way:
+///
+/// ```rust
+/// # use savory::{events::handler_output::Unit, prelude::*};
+/// let button: Node<()> = html::button()
+///     .on_click::<_, Unit>(|_| ())
+///     .on_double_click(|_| Some(()));
+/// ```
+pub trait IntoMsg<Ms, Kind> {
+    fn into_msg(self) -> Option<Ms>;
+}
+
+/// Markers used by [`IntoMsg`] implementations
+pub mod handler_output {
+    /// Handler returns `Ms`
+    pub enum Msg {}
+    /// Handler returns `Option<Ms>`
+    pub enum OptionMsg {}
+    /// Handler returns `()`
+    pub enum Unit {}
+}
+
+impl<Ms> IntoMsg<Ms, handler_output::Msg> for Ms {
+    fn into_msg(self) -> Option<Ms> {
+        Some(self)
+    }
+}
+
+impl<Ms> IntoMsg<Ms, handler_output::OptionMsg> for Option<Ms> {
+    fn into_msg(self) -> Option<Ms> {
+        self
+    }
+}
+
+/// `()` is ignored, unless `Ms` is `()` too
+impl<Ms: 'static> IntoMsg<Ms, handler_output::Unit> for () {
+    fn into_msg(self) -> Option<Ms> {
+        (&mut Some(()) as &mut dyn Any)
+            .downcast_mut::<Option<Ms>>()
+            .and_then(Option::take)
+    }
+}
+
+/// Prefix of the attributes used to track the triggers of the handlers added
+/// using `EventsApi`
+const TRIGGER_PREFIX: &str = "savory-on:";
+
+/// Track `trigger` as one of the triggers `el` has handlers for
+///
+/// Seed keeps the event handlers of elements private, so `EventsApi` tracks
+/// the triggers of the handlers it adds using ignored attributes, which are
+/// never set on the DOM nor rendered into HTML.
+fn track_trigger<Ms>(el: &mut El<Ms>, trigger: &Ev) {
+    el.attrs.add(
+        At::from(format!("{}{}", TRIGGER_PREFIX, trigger)),
+        AtValue::Ignored,
+    );
+}
+
+/// Triggers of the handlers added to `el` using `EventsApi`
+pub(crate) fn triggers<Ms>(el: &El<Ms>) -> Vec<Ev> {
+    el.attrs
+        .vals
+        .iter()
+        .filter(|(_, value)| matches!(value, AtValue::Ignored))
+        .filter_map(|(name, _)| name.as_str().strip_prefix(TRIGGER_PREFIX))
+        .map(|trigger| Ev::from(trigger.to_string()))
+        .collect()
+}
+
+/// Create `EventHandler` that cast the event into `E` before passing it to
+/// `handler`
+pub fn event_handler<Ms, E>(
+    trigger: impl Into<Ev>,
+    handler: impl FnOnce(E) -> Option<Ms> + 'static + Clone,
+) -> EventHandler<Ms>
+where
+    Ms: 'static,
+    E: JsCast + Clone + 'static,
+{
+    EventHandler::new(trigger, move |event: web_sys::Event| {
+        let event = event
+            .dyn_ref::<E>()
+            .expect("event has the type of its trigger")
+            .clone();
+        handler.clone()(event)
+    })
+}
+
+/// Modifiers used by the `_with` event methods
+///
+/// Modifiers can be combined using `|`, e.g. `Modifiers::prevent_default() |
+/// Modifiers::once()`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    prevent_default: bool,
+    stop_propagation: bool,
+    passive: bool,
+    once: bool,
+    capture: bool,
+}
+
+impl Modifiers {
+    /// Call `prevent_default` on the event before calling the handler
+    pub fn prevent_default() -> Self {
+        Self {
+            prevent_default: true,
+            ..Self::default()
+        }
+    }
+
+    /// Call `stop_propagation` on the event before calling the handler
+    pub fn stop_propagation() -> Self {
+        Self {
+            stop_propagation: true,
+            ..Self::default()
+        }
+    }
+
+    /// Listen to the event using passive listener, so the browser doesn't
+    /// wait for the handler before scrolling, this is useful for `on_wheel`
+    /// and `on_touch_move` handlers
+    ///
+    /// Passive handlers cannot prevent the default action of the event.
+    pub fn passive() -> Self {
+        Self {
+            passive: true,
+            ..Self::default()
+        }
+    }
+
+    /// Call the handler only for the first event the element receives
+    pub fn once() -> Self {
+        Self {
+            once: true,
+            ..Self::default()
+        }
+    }
+
+    /// Call the handler in the capture phase, before the handlers of the
+    /// element descendants
+    pub fn capture() -> Self {
+        Self {
+            capture: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            prevent_default: self.prevent_default || other.prevent_default,
+            stop_propagation: self.stop_propagation || other.stop_propagation,
+            passive: self.passive || other.passive,
+            once: self.once || other.once,
+            capture: self.capture || other.capture,
+        }
+    }
+}
+
+/// Add handler to `el` that is called as `modifiers` describe
+///
+/// Seed adds its listeners without options, so passive, capture and once
+/// handlers get their own listener, added with these options to the element
+/// node after it's rendered. That listener redispatch the event to the element
+/// as custom event that Seed handles, so the messages reach the app the same
+/// way other handlers messages do.
+pub fn add_event_handler_with<Ms, E>(
+    el: &mut El<Ms>,
+    trigger: impl Into<Ev>,
+    handler: impl FnOnce(E) -> Option<Ms> + 'static + Clone,
+    modifiers: Modifiers,
+) where
+    Ms: 'static,
+    E: JsCast + Clone + 'static,
+{
+    let trigger = trigger.into();
+    track_trigger(el, &trigger);
+    let call = move |event: web_sys::Event| {
+        if modifiers.prevent_default {
+            event.prevent_default();
+        }
+        if modifiers.stop_propagation {
+            event.stop_propagation();
+        }
+        let event = event
+            .dyn_ref::<E>()
+            .expect("event has the type of its trigger")
+            .clone();
+        handler.clone()(event)
+    };
+
+    if !modifiers.passive && !modifiers.capture && !modifiers.once {
+        el.add_event_handler(EventHandler::new(trigger, call));
+        return;
+    }
+
+    let listener = Rc::new(listener::Listener::new(trigger, modifiers));
+    el.refs.push(listener.node.shared_node_ws.clone());
+    #[cfg(target_arch = "wasm32")]
+    listener::attach_after_render(&listener);
+    el.add_event_handler(EventHandler::new(
+        listener.name.clone(),
+        move |event: web_sys::Event| {
+            // the listener is removed when the view that holds this handler
+            // is dropped
+            let _listener = &listener;
+            let original = event
+                .dyn_ref::<web_sys::CustomEvent>()?
+                .detail()
+                .dyn_into::<web_sys::Event>()
+                .ok()?;
+            call(original)
+        },
+    ));
+}
+
+mod listener {
+    use super::*;
+    use seed::prelude::ElRef;
+    use std::cell::Cell;
+    #[cfg(target_arch = "wasm32")]
+    use {
+        seed::prelude::wasm_bindgen::closure::Closure,
+        std::{cell::RefCell, rc::Weak},
+    };
+
+    thread_local! {
+        static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+    }
+
+    /// Event listener added with the options the modifiers describe, the
+    /// listener is removed from the element node when dropped
+    pub struct Listener {
+        /// Name of the custom event dispatched to the element
+        pub name: String,
+        /// Element node, set by Seed when the element is rendered
+        pub node: ElRef<web_sys::Node>,
+        #[cfg(target_arch = "wasm32")]
+        trigger: Ev,
+        #[cfg(target_arch = "wasm32")]
+        modifiers: Modifiers,
+        #[cfg(target_arch = "wasm32")]
+        attached: RefCell<Option<(web_sys::Node, Closure<dyn Fn(web_sys::Event)>)>>,
+    }
+
+    impl Listener {
+        pub fn new(trigger: Ev, modifiers: Modifiers) -> Self {
+            let id = NEXT_ID.with(|id| id.replace(id.get() + 1));
+            // each listener dispatch its own event, so handlers with the same
+            // trigger don't get each others events
+            let name = format!("savory-listener-{}:{}", id, trigger);
+            #[cfg(not(target_arch = "wasm32"))]
+            let _ = modifiers;
+            Self {
+                name,
+                node: ElRef::new(),
+                #[cfg(target_arch = "wasm32")]
+                trigger,
+                #[cfg(target_arch = "wasm32")]
+                modifiers,
+                #[cfg(target_arch = "wasm32")]
+                attached: RefCell::new(None),
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    thread_local! {
+        // listeners waiting for their element to be rendered
+        static PENDING: RefCell<Vec<Weak<Listener>>> = const { RefCell::new(Vec::new()) };
+        static SCHEDULED: Cell<bool> = const { Cell::new(false) };
+        // once listeners that handled their event, so the listeners added by
+        // later renders of the same element stay off
+        static FIRED: RefCell<Vec<(web_sys::Node, Ev, bool)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Attach `listener` once Seed renders the view that holds it
+    ///
+    /// Seed renders right after calling `view`, and microtasks run after
+    /// that, listeners of elements that are not rendered yet wait for the
+    /// next attach.
+    #[cfg(target_arch = "wasm32")]
+    pub fn attach_after_render(listener: &Rc<Listener>) {
+        PENDING.with(|pending| pending.borrow_mut().push(Rc::downgrade(listener)));
+        if !SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+            seed::spawn_local(async { attach_pending() });
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn attach_pending() {
+        SCHEDULED.with(|scheduled| scheduled.set(false));
+        let pending = PENDING.with(|pending| pending.replace(vec![]));
+        let waiting = pending
+            .into_iter()
+            // listeners of dropped views are gone
+            .filter(|listener| {
+                listener
+                    .upgrade()
+                    .map_or(false, |listener| !listener.attach())
+            })
+            .collect::<Vec<_>>();
+        PENDING.with(|pending| pending.borrow_mut().extend(waiting));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    impl Listener {
+        /// Add the listener to the element node, returns `false` if the
+        /// element is not rendered yet
+        fn attach(&self) -> bool {
+            let node = match self.node.get() {
+                Some(node) => node,
+                None => return false,
+            };
+            let key = (node.clone(), self.trigger.clone(), self.modifiers.capture);
+            let once = self.modifiers.once;
+            if once && FIRED.with(|fired| fired.borrow().contains(&key)) {
+                return true;
+            }
+
+            let callback = {
+                let node = node.clone();
+                let name = self.name.clone();
+                move |event: web_sys::Event| {
+                    if once {
+                        FIRED.with(|fired| {
+                            let mut fired = fired.borrow_mut();
+                            fired.retain(|(node, ..)| node.is_connected());
+                            fired.push(key.clone());
+                        });
+                    }
+                    let mut init = web_sys::CustomEventInit::new();
+                    init.detail(&event);
+                    if let Ok(custom) = web_sys::CustomEvent::new_with_event_init_dict(&name, &init)
+                    {
+                        let _ = node.dispatch_event(&custom);
+                    }
+                }
+            };
+            let callback = Closure::wrap(Box::new(callback) as Box<dyn Fn(web_sys::Event)>);
+            let mut options = web_sys::AddEventListenerOptions::new();
+            options
+                .capture(self.modifiers.capture)
+                .passive(self.modifiers.passive)
+                .once(once);
+            node.add_event_listener_with_callback_and_add_event_listener_options(
+                self.trigger.as_str(),
+                callback.as_ref().unchecked_ref(),
+                &options,
+            )
+            .expect("add event listener");
+            *self.attached.borrow_mut() = Some((node, callback));
+            true
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            if let Some((node, callback)) = self.attached.get_mut().take() {
+                let _ = node.remove_event_listener_with_callback_and_bool(
+                    self.trigger.as_str(),
+                    callback.as_ref().unchecked_ref(),
+                    self.modifiers.capture,
+                );
+            }
+        }
+    }
+}
+
 macro_rules! events_api {
-    ( $( $event:ident: $ty:ty { $( $(#[$doc:meta])* $name:ident = $ev:expr $(,)? )* } $(,)? )* ) => {
+    ( $( $ty:ty { $( $(#[$doc:meta])* $name:ident = $ev:expr $(,)? )* } $(,)? )* ) => {
         pub trait EventsApi<Msg: 'static> {
             $(
                 $(
                     $( #[$doc] )*
-                    fn $name<EMsg: 'static>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self;
+                    fn $name<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self;
+
+                    paste! {
+                        #[doc = "Same as `" $name "` but the handler is called as `modifiers` describe"]
+                        fn [<$name _with>]<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone, modifiers: Modifiers) -> Self;
+                    }
                 )*
             )*
+
+            /// Send `msg` when `combo` is pressed while the element has focus,
+            /// the default action of the pressed keys is prevented
+            fn on_shortcut(self, combo: impl Into<KeyCombo>, msg: Msg) -> Self
+            where
+                Msg: Clone;
         }
 
         impl<Msg: 'static> EventsApi<Msg> for El<Msg> {
             $(
                 $(
-                    fn $name<EMsg: 'static>(mut self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self {
-                        self.add_event_handler($event($ev, handler));
+                    fn $name<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(mut self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self {
+                        track_trigger(&mut self, &$ev);
+                        self.add_event_handler(event_handler::<Msg, $ty>($ev, move |ev| handler.clone()(ev).into_msg()));
                         self
                     }
+
+                    paste! {
+                        fn [<$name _with>]<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(mut self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone, modifiers: Modifiers) -> Self {
+                            add_event_handler_with::<Msg, $ty>(&mut self, $ev, move |ev| handler.clone()(ev).into_msg(), modifiers);
+                            self
+                        }
+                    }
                 )*
             )*
+
+            fn on_shortcut(self, combo: impl Into<KeyCombo>, msg: Msg) -> Self
+            where
+                Msg: Clone,
+            {
+                let combo = combo.into();
+                self.on_key_down(move |ev| {
+                    if combo.matches_event(&ev) {
+                        ev.prevent_default();
+                        Some(msg)
+                    } else {
+                        None
+                    }
+                })
+            }
         }
 
         impl<Msg: 'static> EventsApi<Msg> for Node<Msg> {
             $(
                 $(
-                    fn $name<EMsg: 'static>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self {
+                    fn $name<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self {
                         self.and_el(|el| el.$name(handler))
                     }
+
+                    paste! {
+                        fn [<$name _with>]<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone, modifiers: Modifiers) -> Self {
+                            self.and_el(|el| el.[<$name _with>](handler, modifiers))
+                        }
+                    }
                 )*
             )*
+
+            fn on_shortcut(self, combo: impl Into<KeyCombo>, msg: Msg) -> Self
+            where
+                Msg: Clone,
+            {
+                self.and_el(|el| el.on_shortcut(combo, msg))
+            }
+        }
+
+        impl<T, Msg: 'static> EventsApi<Msg> for HtmlEl<T, Msg> {
+            $(
+                $(
+                    fn $name<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone) -> Self {
+                        self.and_el(|el| el.$name(handler))
+                    }
+
+                    paste! {
+                        fn [<$name _with>]<EMsg: IntoMsg<Msg, Kind> + 'static, Kind>(self, handler: impl FnOnce($ty) -> EMsg + 'static + Clone, modifiers: Modifiers) -> Self {
+                            self.and_el(|el| el.[<$name _with>](handler, modifiers))
+                        }
+                    }
+                )*
+            )*
+
+            fn on_shortcut(self, combo: impl Into<KeyCombo>, msg: Msg) -> Self
+            where
+                Msg: Clone,
+            {
+                self.and_el(|el| el.on_shortcut(combo, msg))
+            }
         }
     }
 }
 
 events_api! {
-    ev: web_sys::Event {
+    web_sys::Event {
         on_scroll = Ev::Scroll,
         on_after_print = Ev::AfterPrint,
         on_before_print = Ev::BeforePrint,
@@ -73,13 +577,13 @@ events_api! {
         on_selectionchange = Ev::from("selectionchange"),
         on_submit = Ev::Submit,
     }
-    focus_ev: web_sys::FocusEvent {
+    web_sys::FocusEvent {
         on_blur = Ev::Blur,
         on_focus = Ev::Focus,
         on_focus_in = Ev::from("focusin"),
         on_focus_out = Ev::from("focusout"),
     }
-    mouse_ev: web_sys::MouseEvent {
+    web_sys::MouseEvent {
         on_aux_click = Ev::AuxClick,
         on_click = Ev::Click,
         on_double_click = Ev::DblClick,
@@ -92,7 +596,7 @@ events_api! {
         on_mouse_up = Ev::MouseUp,
         on_context_menu = Ev::ContextMenu,
     }
-    pointer_ev: web_sys::PointerEvent {
+    web_sys::PointerEvent {
         on_pointer_cancel = Ev::PointerCancel,
         on_pointer_down = Ev::PointerDown,
         on_pointer_enter = Ev::PointerEnter,
@@ -104,26 +608,26 @@ events_api! {
         on_lost_pointer_capture = Ev::LostPointerCapture,
         on_got_pointer_capture = Ev::GotPointerCapture,
     }
-    wheel_ev: web_sys::WheelEvent {
+    web_sys::WheelEvent {
         on_wheel = Ev::Wheel,
     }
     // NOTE: that `InputEvent` doesn't provied access to data property yet, use
     // seed::browser::util::{get_value, set_value} to access these value for
     // now.
-    input_ev: web_sys::InputEvent {
+    web_sys::InputEvent {
         on_before_input = Ev::from("beforeinput")
         on_input = Ev::Input,
     }
-    keyboard_ev: web_sys::KeyboardEvent {
+    web_sys::KeyboardEvent {
         on_key_down = Ev::KeyDown,
         on_key_up = Ev::KeyUp,
     }
-    composition_ev: web_sys::CompositionEvent {
+    web_sys::CompositionEvent {
         on_composition_start = Ev::CompositionStart,
         on_composition_update = Ev::CompositionUpdate,
         on_composition_end = Ev::CompositionEnd,
     }
-    drag_ev: web_sys::DragEvent {
+    web_sys::DragEvent {
         on_drag = Ev::Drag,
         on_drag_end = Ev::DragEnd,
         on_drag_enter = Ev::DragEnter,
@@ -132,11 +636,11 @@ events_api! {
         on_drag_start = Ev::DragStart,
         on_drop = Ev::Drop,
     }
-    ui_ev: web_sys::UiEvent {
+    web_sys::UiEvent {
         on_resize = Ev::Resize,
         on_select = Ev::Select,
     }
-    touch_ev: web_sys::TouchEvent {
+    web_sys::TouchEvent {
         on_touch_start = Ev::TouchStart,
         on_touch_end = Ev::TouchEnd,
         on_touch_cancel = Ev::TouchCancel,
@@ -144,41 +648,29 @@ events_api! {
     }
 }
 
-macro_rules! event_creator{
-    ( $( $(#[$doc:meta])? $name:ident($ty:ty) $(,)? )* ) => {
-        $(
-            $( #[$doc] )?
-            fn $name<Ms: 'static, HandlerMs: 'static>(
-                trigger: impl Into<Ev>,
-                handler: impl FnOnce($ty) -> HandlerMs + 'static + Clone,
-            ) -> EventHandler<Ms> {
-                // TODO: Supoort Option<Ms> as in Seed API
-                let msg_type = TypeId::of::<HandlerMs>();
-                if msg_type != TypeId::of::<Ms>() && msg_type != TypeId::of::<()>() {
-                    panic!("Handler can return only Ms or ()!");
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                let closure_handler = move |event: web_sys::Event| {
-                    let output = &mut Some(handler.clone()(event.dyn_ref::<$ty>().unwrap().clone())) as &mut dyn Any;
-                    output.downcast_mut::<Option<Ms>>().and_then(Option::take)
-                };
-                EventHandler::new(trigger, closure_handler)
-            }
-        )*
-    }
-}
-
-event_creator! {
-    /// create `EventHandler` with `web_sys::WheelEvent`
-    wheel_ev(web_sys::WheelEvent),
-    /// create `EventHandler` with `web_sys::CompositionEvent`
-    composition_ev(web_sys::CompositionEvent),
-    /// create `EventHandler` with `web_sys::FocusEvent`
-    focus_ev(web_sys::FocusEvent),
-    /// create `EventHandler` with `web_sys::DragEvent`
-    drag_ev(web_sys::DragEvent),
-    /// create `EventHandler` with `web_sys::InputEvent`
-    input_ev(web_sys::InputEvent),
-    /// create `EventHandler` with `web_sys::UiEvent`
-    ui_ev(web_sys::UiEvent),
+    #[derive(Debug, PartialEq)]
+    enum Msg {
+        Save,
+    }
+
+    #[test]
+    fn test_handler_output() {
+        let _: Node<Msg> = html::button()
+            .on_click(|_| ())
+            .on_click(|_| Msg::Save)
+            .on_click(|_| None)
+            .on_click_with(|_| Some(Msg::Save), Modifiers::once());
+        let _: Node<()> = html::button()
+            .on_click::<_, handler_output::Unit>(|_| ())
+            .on_click(|_| Some(()));
+
+        assert_eq!(IntoMsg::<Msg, _>::into_msg(Msg::Save), Some(Msg::Save));
+        assert_eq!(IntoMsg::<Msg, _>::into_msg(None::<Msg>), None);
+        assert_eq!(IntoMsg::<Msg, _>::into_msg(()), None);
+        assert_eq!(IntoMsg::<(), handler_output::Unit>::into_msg(()), Some(()));
+    }
 }
@@ -0,0 +1,357 @@
+//! Typed keyboard keys and shortcuts.
+
+use seed::futures::{channel::mpsc, Stream};
+use std::{fmt, str::FromStr};
+
+/// Keyboard key, as reported by `KeyboardEvent::key()`
+///
+/// Character keys are stored in lower case, so `Key::Char('s')` is the key
+/// for both `s` and `S`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Space,
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// Function keys, e.g. `Key::F(5)` for `F5`
+    F(u8),
+    Control,
+    Shift,
+    Alt,
+    Meta,
+    /// Any other key
+    Other(String),
+}
+
+impl Key {
+    /// Key of the keyboard `event`
+    pub fn from_event(event: &web_sys::KeyboardEvent) -> Self {
+        Key::from(event.key().as_str())
+    }
+}
+
+impl From<&str> for Key {
+    /// Convert `KeyboardEvent::key()` value into `Key`, this also accepts the
+    /// names used in `Display` (e.g. `Space`) and few common aliases (e.g.
+    /// `Esc`)
+    fn from(key: &str) -> Self {
+        let mut chars = key.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return match c {
+                ' ' => Key::Space,
+                c => Key::Char(c.to_lowercase().next().unwrap_or(c)),
+            };
+        }
+
+        match key {
+            "Space" | "Spacebar" => Key::Space,
+            "Enter" => Key::Enter,
+            "Escape" | "Esc" => Key::Escape,
+            "Tab" => Key::Tab,
+            "Backspace" => Key::Backspace,
+            "Delete" | "Del" => Key::Delete,
+            "Insert" => Key::Insert,
+            "ArrowUp" | "Up" => Key::ArrowUp,
+            "ArrowDown" | "Down" => Key::ArrowDown,
+            "ArrowLeft" | "Left" => Key::ArrowLeft,
+            "ArrowRight" | "Right" => Key::ArrowRight,
+            "Home" => Key::Home,
+            "End" => Key::End,
+            "PageUp" => Key::PageUp,
+            "PageDown" => Key::PageDown,
+            "Control" | "Ctrl" => Key::Control,
+            "Shift" => Key::Shift,
+            "Alt" => Key::Alt,
+            "Meta" => Key::Meta,
+            key => match key.strip_prefix('F').and_then(|n| n.parse().ok()) {
+                Some(n) => Key::F(n),
+                None => Key::Other(key.to_string()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Char(c) => write!(f, "{}", c.to_uppercase()),
+            Key::F(n) => write!(f, "F{}", n),
+            Key::Other(key) => f.write_str(key),
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// Key combined with modifier keys, e.g. `Ctrl+Shift+S`
+///
+/// ```rust
+/// # use savory::prelude::*;
+/// let save = KeyCombo::new(Key::Char('s')).ctrl();
+/// assert_eq!(save, "Ctrl+S".parse().unwrap());
+/// assert_eq!(save.to_string(), "Ctrl+S");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+        }
+    }
+
+    /// Key combination of the keyboard `event`
+    pub fn from_event(event: &web_sys::KeyboardEvent) -> Self {
+        Self {
+            key: Key::from_event(event),
+            ctrl: event.ctrl_key(),
+            shift: event.shift_key(),
+            alt: event.alt_key(),
+            meta: event.meta_key(),
+        }
+    }
+
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    pub fn meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+
+    /// Check if `pressed` key combination triggers this combination
+    ///
+    /// Shift is ignored for non-letter characters, since it's usually needed
+    /// to type them (e.g. `?`).
+    pub fn matches(&self, pressed: &KeyCombo) -> bool {
+        let shift_ignored = matches!(self.key, Key::Char(c) if !c.is_alphabetic());
+        self.key == pressed.key
+            && self.ctrl == pressed.ctrl
+            && self.alt == pressed.alt
+            && self.meta == pressed.meta
+            && (shift_ignored || self.shift == pressed.shift)
+    }
+
+    /// Check if the keyboard `event` triggers this combination
+    pub fn matches_event(&self, event: &web_sys::KeyboardEvent) -> bool {
+        self.matches(&KeyCombo::from_event(event))
+    }
+}
+
+impl From<Key> for KeyCombo {
+    fn from(key: Key) -> Self {
+        KeyCombo::new(key)
+    }
+}
+
+impl From<char> for KeyCombo {
+    fn from(c: char) -> Self {
+        KeyCombo::new(Key::from(c.to_string().as_str()))
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.ctrl, "Ctrl+"),
+            (self.shift, "Shift+"),
+            (self.alt, "Alt+"),
+            (self.meta, "Meta+"),
+        ];
+        for (_, name) in modifiers.iter().filter(|(pressed, _)| *pressed) {
+            f.write_str(name)?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Error returned when parsing invalid key combination
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyComboError {
+    pub combo: String,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for KeyComboError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid key combination `{}`: {}",
+            self.combo, self.reason
+        )
+    }
+}
+
+impl std::error::Error for KeyComboError {}
+
+impl FromStr for KeyCombo {
+    type Err = KeyComboError;
+
+    /// Parse key combinations such as `Ctrl+Shift+S`, `Alt+ArrowUp` or
+    /// `Ctrl++`, modifier names are case insensitive
+    fn from_str(combo: &str) -> Result<Self, Self::Err> {
+        let err = |reason| KeyComboError {
+            combo: combo.to_string(),
+            reason,
+        };
+
+        let trimmed = combo.trim();
+        let (modifiers, key) = match trimmed.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => trimmed.rsplit_once('+').unwrap_or(("", trimmed)),
+        };
+        if key.is_empty() {
+            return Err(err("missing key"));
+        }
+
+        let mut result = KeyCombo::new(Key::from(key));
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => result.ctrl = true,
+                "shift" => result.shift = true,
+                "alt" | "option" => result.alt = true,
+                "meta" | "cmd" | "super" => result.meta = true,
+                _ => return Err(err("unknown modifier")),
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Window-level shortcuts registry
+///
+/// Elements usually subscribe to shortcuts using
+/// [`OrdersExt::subscribe_shortcut`] instead of the functions in this module.
+///
+/// [`OrdersExt::subscribe_shortcut`]: crate::prelude::OrdersExt::subscribe_shortcut
+pub mod shortcuts {
+    use super::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static SUBSCRIBERS: RefCell<Vec<(KeyCombo, mpsc::UnboundedSender<()>)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Stream that yields every time `combo` is pressed in the window
+    pub fn listen(combo: impl Into<KeyCombo>) -> impl Stream<Item = ()> {
+        #[cfg(target_arch = "wasm32")]
+        listen_to_window();
+        let (sender, receiver) = mpsc::unbounded();
+        SUBSCRIBERS.with(|subscribers| subscribers.borrow_mut().push((combo.into(), sender)));
+        receiver
+    }
+
+    /// Notify the subscribers of the shortcuts that `pressed` triggers,
+    /// returns `true` if there was any subscriber
+    ///
+    /// This is called for every `keydown` event in the window, it can also be
+    /// used to trigger shortcuts manually (e.g. in tests).
+    pub fn dispatch(pressed: &KeyCombo) -> bool {
+        SUBSCRIBERS.with(|subscribers| {
+            let mut handled = false;
+            subscribers.borrow_mut().retain(|(combo, sender)| {
+                if !combo.matches(pressed) {
+                    return !sender.is_closed();
+                }
+                let sent = sender.unbounded_send(()).is_ok();
+                handled |= sent;
+                sent
+            });
+            handled
+        })
+    }
+
+    /// Add `keydown` listener to the window, if it's not added yet
+    #[cfg(target_arch = "wasm32")]
+    fn listen_to_window() {
+        use seed::prelude::{wasm_bindgen::closure::Closure, JsCast};
+
+        thread_local! {
+            static LISTENING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+        }
+
+        if LISTENING.with(|listening| listening.replace(true)) {
+            return;
+        }
+
+        let listener = Closure::wrap(Box::new(|event: web_sys::KeyboardEvent| {
+            // keys held by the user fire repeated events, a shortcut is
+            // triggered only once per press
+            if !event.repeat() && dispatch(&KeyCombo::from_event(&event)) {
+                event.prevent_default();
+            }
+        }) as Box<dyn Fn(web_sys::KeyboardEvent)>);
+        seed::browser::util::window()
+            .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+            .expect("add shortcuts listener");
+        // the listener lives as long as the window
+        listener.forget();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seed::futures::{FutureExt, StreamExt};
+
+    #[test]
+    fn test_parse_combo() {
+        let combo = "ctrl+Shift+s".parse::<KeyCombo>().unwrap();
+        assert_eq!(combo, KeyCombo::new(Key::Char('s')).ctrl().shift());
+        assert_eq!(combo.to_string(), "Ctrl+Shift+S");
+        assert_eq!("Ctrl++".parse(), Ok(KeyCombo::new(Key::Char('+')).ctrl()));
+        assert_eq!("F5".parse(), Ok(KeyCombo::new(Key::F(5))));
+        assert_eq!("Alt+Space".parse(), Ok(KeyCombo::new(Key::Space).alt()));
+        assert!("Hyper+A".parse::<KeyCombo>().is_err());
+        assert!("Ctrl+".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        let mut save = shortcuts::listen(KeyCombo::new(Key::Char('s')).ctrl());
+        let help = shortcuts::listen('?');
+
+        assert!(!shortcuts::dispatch(&KeyCombo::new(Key::Char('s'))));
+        assert!(shortcuts::dispatch(&"Ctrl+S".parse().unwrap()));
+        assert_eq!(save.next().now_or_never(), Some(Some(())));
+
+        // shift is needed to type `?`
+        drop(help);
+        assert!(!shortcuts::dispatch(&"Shift+?".parse().unwrap()));
+        assert!(KeyCombo::from('?').matches(&"Shift+?".parse().unwrap()));
+    }
+}
@@ -1,16 +1,79 @@
 //! Functions used to create HTML nodes
+//!
+//! Element constructors return plain `Node`, global attributes (e.g. `class`,
+//! `id` and `title`) are available for all elements through `AttributeApi`,
+//! attributes that belong to specific elements (e.g. `href` and `disabled`)
+//! are available through `UntypedAttributeApi`, and any other attribute can be
+//! set using `set_attr`.
+//!
+//! Elements that have their own attributes in the HTML spec (e.g. `input`, `a`
+//! and `form`) can also be created using the constructors in the [`typed`]
+//! module, these return [`HtmlEl`], which provides typed methods for the
+//! attributes of the element only:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! let email: Node<()> = html::typed::input()
+//!     .type_(html::InputType::Email)
+//!     .name("email")
+//!     .required(true)
+//!     .into();
+//!
+//! let link: Node<()> = html::typed::a()
+//!     .href("https://example.com")
+//!     .target(html::Target::Blank)
+//!     .rel("noopener")
+//!     .push("Example")
+//!     .into();
+//! ```
+//!
+//! Using attributes that don't belong to the element is a compile error:
+//!
+//! ```rust,compile_fail
+//! # use savory::prelude::*;
+//! let input: Node<()> = html::typed::input().href("https://example.com").into();
+//! ```
+//!
+//! Every element constructor has an argument-taking variant that ends with
+//! `_with`, these accept [`Children`] such as text, nodes, tuples and
+//! iterators:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! let node: Node<()> = html::div_with((
+//!     html::h1_with("Title"),
+//!     html::p_with("Some text"),
+//! ));
+//!
+//! assert_eq!(node.to_html(), "<div><h1>Title</h1><p>Some text</p></div>");
+//! ```
 
+use crate::prelude::*;
+use paste::paste;
 use seed::prelude::{El, Node, Tag, UpdateEl};
 use std::borrow::Cow;
 
-// TODO: support arguments in some functions, e.g. h1("title"),
-// div(vec![child, child]), p("text")
+pub use children::{children_iter, Children, ChildrenIter};
+pub use el::HtmlEl;
+pub use values::*;
+
+mod children;
+mod el;
+mod values;
+
 macro_rules! create_html_elements {
     ( $( $fn_name:ident => $tag:stmt $(,)? )* ) => {
         $(
             pub fn $fn_name<Msg>() -> Node<Msg> {
                 $tag
             }
+
+            paste! {
+                #[doc = concat!("Same as [`", stringify!($fn_name), "`] but with `children`")]
+                pub fn [<$fn_name _with>]<Msg>(children: impl Children<Msg>) -> Node<Msg> {
+                    $fn_name().push(children.into_children())
+                }
+            }
         )*
     };
 }
@@ -237,3 +300,448 @@ create_html_elements! {
     view => view!(),
     // placeholder => placeholder!(),
 }
+
+macro_rules! typed_attr_fns {
+    (String $attr_name:literal $attr_ident:ident) => {
+        pub fn $attr_ident(self, val: impl ToString) -> Self {
+            self.set_attr($attr_name, val)
+        }
+
+        paste! {
+            pub fn [<try_ $attr_ident>](self, val: Option<impl ToString>) -> Self {
+                match val {
+                    Some(val) => self.$attr_ident(val),
+                    None => self,
+                }
+            }
+
+            pub fn [<get_ $attr_ident>](&self) -> Option<&str> {
+                self.get_attr($attr_name)
+            }
+        }
+    };
+    (bool $attr_name:literal $attr_ident:ident) => {
+        pub fn $attr_ident(self, val: bool) -> Self {
+            self.toggle_attr($attr_name, val)
+        }
+
+        paste! {
+            pub fn [<get_ $attr_ident>](&self) -> bool {
+                self.has_attr($attr_name)
+            }
+        }
+    };
+    ($ty:ident $attr_name:literal $attr_ident:ident) => {
+        pub fn $attr_ident(self, val: $ty) -> Self {
+            self.set_attr($attr_name, val)
+        }
+
+        paste! {
+            pub fn [<try_ $attr_ident>](self, val: Option<$ty>) -> Self {
+                match val {
+                    Some(val) => self.$attr_ident(val),
+                    None => self,
+                }
+            }
+
+            /// Returns `Some(Err(..))` if the attribute value is not valid
+            pub fn [<get_ $attr_ident>](&self) -> Option<Result<$ty, $crate::html::AttrValueError>> {
+                self.get_attr($attr_name).map(|val| {
+                    val.parse::<$ty>().map_err(|_| $crate::html::AttrValueError {
+                        value: val.to_string(),
+                        expected: stringify!($ty),
+                    })
+                })
+            }
+        }
+    };
+}
+
+pub(crate) use typed_attr_fns;
+
+macro_rules! create_typed_html_elements {
+    ( $(
+        $fn_name:ident => $marker:ident($tag:expr) {
+            $( $attr_name:literal: $attr_ident:ident: $ty:ident $(,)? )*
+        } $(,)?
+    )* ) => {
+        /// Marker types used by [`HtmlEl`] to decide which attribute methods
+        /// are available
+        pub mod tags {
+            $(
+                #[doc = concat!("Marker for `<", stringify!($fn_name), ">` elements")]
+                pub enum $marker {}
+            )*
+        }
+
+        $(
+            pub fn $fn_name<Msg>() -> HtmlEl<tags::$marker, Msg> {
+                HtmlEl::new($tag)
+            }
+
+            paste! {
+                #[doc = concat!("Same as [`", stringify!($fn_name), "`] but with `children`")]
+                pub fn [<$fn_name _with>]<Msg>(
+                    children: impl $crate::html::Children<Msg>,
+                ) -> HtmlEl<tags::$marker, Msg> {
+                    $fn_name().push(children.into_children())
+                }
+            }
+
+            impl<Msg> HtmlEl<tags::$marker, Msg> {
+                $( typed_attr_fns!($ty $attr_name $attr_ident); )*
+            }
+        )*
+    };
+}
+
+pub(crate) use create_typed_html_elements;
+
+/// Constructors of elements that have their own attributes in the HTML spec,
+/// these create [`HtmlEl`] which provides typed methods for these attributes
+pub mod typed {
+    use super::*;
+
+    // Attributes are taken from the HTML spec:
+    // https://html.spec.whatwg.org/multipage/indices.html#attributes-3
+    create_typed_html_elements! {
+        a => A(a!()) {
+            "href": href: String,
+            "target": target: Target,
+            "download": download: String,
+            "ping": ping: String,
+            "rel": rel: String,
+            "hreflang": href_lang: String,
+            "type": type_: String,
+            "referrerpolicy": referrer_policy: ReferrerPolicy,
+        }
+        area => Area(area!()) {
+            "alt": alt: String,
+            "coords": coords: String,
+            "shape": shape: String,
+            "href": href: String,
+            "target": target: Target,
+            "download": download: String,
+            "ping": ping: String,
+            "rel": rel: String,
+            "referrerpolicy": referrer_policy: ReferrerPolicy,
+        }
+        audio => Audio(audio!()) {
+            "src": src: String,
+            "crossorigin": cross_origin: CrossOrigin,
+            "preload": preload: Preload,
+            "autoplay": auto_play: bool,
+            "loop": loop_: bool,
+            "muted": muted: bool,
+            "controls": controls: bool,
+        }
+        video => Video(video!()) {
+            "src": src: String,
+            "crossorigin": cross_origin: CrossOrigin,
+            "poster": poster: String,
+            "preload": preload: Preload,
+            "autoplay": auto_play: bool,
+            "playsinline": plays_inline: bool,
+            "loop": loop_: bool,
+            "muted": muted: bool,
+            "controls": controls: bool,
+            "width": width: u32,
+            "height": height: u32,
+        }
+        img => Img(img!()) {
+            "alt": alt: String,
+            "src": src: String,
+            "srcset": src_set: String,
+            "sizes": sizes: String,
+            "crossorigin": cross_origin: CrossOrigin,
+            "usemap": use_map: String,
+            "ismap": is_map: bool,
+            "width": width: u32,
+            "height": height: u32,
+            "referrerpolicy": referrer_policy: ReferrerPolicy,
+            "decoding": decoding: Decoding,
+            "loading": loading: Loading,
+        }
+        embed => Embed(embed!()) {
+            "src": src: String,
+            "type": type_: String,
+            "width": width: u32,
+            "height": height: u32,
+        }
+        iframe => Iframe(iframe!()) {
+            "src": src: String,
+            "srcdoc": src_doc: String,
+            "name": name: String,
+            "sandbox": sandbox: String,
+            "allow": allow: String,
+            "allowfullscreen": allow_full_screen: bool,
+            "width": width: u32,
+            "height": height: u32,
+            "referrerpolicy": referrer_policy: ReferrerPolicy,
+            "loading": loading: Loading,
+        }
+        object => Object(object!()) {
+            "data": data: String,
+            "type": type_: String,
+            "name": name: String,
+            "form": form: String,
+            "width": width: u32,
+            "height": height: u32,
+        }
+        source => Source(source!()) {
+            "type": type_: String,
+            "src": src: String,
+            "srcset": src_set: String,
+            "sizes": sizes: String,
+            "media": media: String,
+            "width": width: u32,
+            "height": height: u32,
+        }
+        track => Track(track!()) {
+            "kind": kind: TrackKind,
+            "src": src: String,
+            "srclang": src_lang: String,
+            "label": label: String,
+            "default": default: bool,
+        }
+        canvas => Canvas(canvas!()) {
+            "width": width: u32,
+            "height": height: u32,
+        }
+        script => Script(Script!()) {
+            "src": src: String,
+            "type": type_: String,
+            "nomodule": no_module: bool,
+            "async": async_: bool,
+            "defer": defer: bool,
+            "crossorigin": cross_origin: CrossOrigin,
+            "integrity": integrity: String,
+            "referrerpolicy": referrer_policy: ReferrerPolicy,
+        }
+        blockquote => Blockquote(blockquote!()) {
+            "cite": cite: String,
+        }
+        q => Q(q!()) {
+            "cite": cite: String,
+        }
+        del => Del(del!()) {
+            "cite": cite: String,
+            "datetime": date_time: String,
+        }
+        ins => Ins(ins!()) {
+            "cite": cite: String,
+            "datetime": date_time: String,
+        }
+        time => Time(time!()) {
+            "datetime": date_time: String,
+        }
+        data => Data(data!()) {
+            "value": value: String,
+        }
+        li => Li(li!()) {
+            "value": value: i32,
+        }
+        ol => Ol(ol!()) {
+            "reversed": reversed: bool,
+            "start": start: i32,
+            "type": type_: ListType,
+        }
+        map => Map(map!()) {
+            "name": name: String,
+        }
+        col => Col(col!()) {
+            "span": span: u32,
+        }
+        colgroup => Colgroup(colgroup!()) {
+            "span": span: u32,
+        }
+        td => Td(td!()) {
+            "colspan": col_span: u32,
+            "rowspan": row_span: u32,
+            "headers": headers: String,
+        }
+        th => Th(th!()) {
+            "colspan": col_span: u32,
+            "rowspan": row_span: u32,
+            "headers": headers: String,
+            "scope": scope: Scope,
+            "abbr": abbr: String,
+        }
+        form => Form(form!()) {
+            "accept-charset": accept_charset: String,
+            "action": action: String,
+            "autocomplete": auto_complete: String,
+            "enctype": enc_type: EncType,
+            "method": method: FormMethod,
+            "name": name: String,
+            "novalidate": no_validate: bool,
+            "target": target: Target,
+            "rel": rel: String,
+        }
+        fieldset => Fieldset(fieldset!()) {
+            "disabled": disabled: bool,
+            "form": form: String,
+            "name": name: String,
+        }
+        label => Label(label!()) {
+            "for": for_: String,
+        }
+        input => Input(input!()) {
+            "type": type_: InputType,
+            "name": name: String,
+            "value": value: String,
+            "placeholder": placeholder: String,
+            "checked": checked: bool,
+            "disabled": disabled: bool,
+            "readonly": read_only: bool,
+            "required": required: bool,
+            "multiple": multiple: bool,
+            "min": min: String,
+            "max": max: String,
+            "step": step: String,
+            "minlength": min_length: u32,
+            "maxlength": max_length: u32,
+            "size": size: u32,
+            "pattern": pattern: String,
+            "list": list: String,
+            "accept": accept: String,
+            "alt": alt: String,
+            "src": src: String,
+            "width": width: u32,
+            "height": height: u32,
+            "autocomplete": auto_complete: String,
+            "dirname": dir_name: String,
+            "form": form: String,
+            "formaction": form_action: String,
+            "formenctype": form_enc_type: EncType,
+            "formmethod": form_method: FormMethod,
+            "formnovalidate": form_no_validate: bool,
+            "formtarget": form_target: Target,
+        }
+        button => Button(button!()) {
+            "type": type_: ButtonType,
+            "name": name: String,
+            "value": value: String,
+            "disabled": disabled: bool,
+            "form": form: String,
+            "formaction": form_action: String,
+            "formenctype": form_enc_type: EncType,
+            "formmethod": form_method: FormMethod,
+            "formnovalidate": form_no_validate: bool,
+            "formtarget": form_target: Target,
+        }
+        select => Select(select!()) {
+            "name": name: String,
+            "disabled": disabled: bool,
+            "required": required: bool,
+            "multiple": multiple: bool,
+            "size": size: u32,
+            "autocomplete": auto_complete: String,
+            "form": form: String,
+        }
+        optgroup => Optgroup(optgroup!()) {
+            "disabled": disabled: bool,
+            "label": label: String,
+        }
+        option => Option_(option!()) {
+            "disabled": disabled: bool,
+            "label": label: String,
+            "selected": selected: bool,
+            "value": value: String,
+        }
+        textarea => Textarea(textarea!()) {
+            "name": name: String,
+            "placeholder": placeholder: String,
+            "disabled": disabled: bool,
+            "readonly": read_only: bool,
+            "required": required: bool,
+            "rows": rows: u32,
+            "cols": cols: u32,
+            "minlength": min_length: u32,
+            "maxlength": max_length: u32,
+            "wrap": wrap: Wrap,
+            "autocomplete": auto_complete: String,
+            "dirname": dir_name: String,
+            "form": form: String,
+        }
+        output => Output(output!()) {
+            "for": for_: String,
+            "form": form: String,
+            "name": name: String,
+        }
+        meter => Meter(meter!()) {
+            "value": value: f64,
+            "min": min: f64,
+            "max": max: f64,
+            "low": low: f64,
+            "high": high: f64,
+            "optimum": optimum: f64,
+        }
+        progress => Progress(progress!()) {
+            "value": value: f64,
+            "max": max: f64,
+        }
+        details => Details(details!()) {
+            "open": open: bool,
+        }
+        dialog => Dialog(dialog!()) {
+            "open": open: bool,
+        }
+        // SVG elements
+        image => Image(image!()) {
+            "href": href: String,
+            "x": x: String,
+            "y": y: String,
+            "width": width: String,
+            "height": height: String,
+            "preserveAspectRatio": preserve_aspect_ratio: String,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_getters() {
+        let input: HtmlEl<typed::tags::Input, ()> =
+            typed::input().set_attr("type", "TEXT").max_length(8);
+        assert_eq!(input.get_type_(), Some(Ok(InputType::Text)));
+        assert_eq!(input.get_max_length(), Some(Ok(8)));
+        assert_eq!(input.get_size(), None);
+
+        let input: HtmlEl<typed::tags::Input, ()> = input
+            .set_attr("type", "unknown")
+            .set_attr("maxlength", "-1")
+            .set_attr("tabindex", "first");
+        let error = |value: &str, expected| AttrValueError {
+            value: value.to_string(),
+            expected,
+        };
+        assert_eq!(input.get_type_(), Some(Err(error("unknown", "InputType"))));
+        assert_eq!(input.get_max_length(), Some(Err(error("-1", "u32"))));
+        assert_eq!(input.get_tab_index(), Some(Err(error("first", "i32"))));
+    }
+
+    #[test]
+    fn test_untyped_attributes() {
+        let marker: Node<()> = marker().view_box("0 0 10 10").x(5).y(5);
+        assert_eq!(marker.get_view_box(), Some("0 0 10 10"));
+        assert_eq!(
+            marker.to_html(),
+            r#"<marker xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10" x="5" y="5"/>"#
+        );
+
+        let meta: Node<()> = custom("meta")
+            .name("viewport")
+            .content("width=device-width");
+        assert_eq!(meta.get_name(), Some("viewport"));
+        assert_eq!(meta.get_content(), Some("width=device-width"));
+
+        let button: Node<()> = button().type_("submit").disabled(true);
+        assert!(button.get_disabled());
+        assert_eq!(button.get_type_(), Some("submit"));
+        assert!(!button.disabled(false).get_disabled());
+    }
+}
@@ -0,0 +1,169 @@
+use super::HtmlEl;
+use crate::prelude::*;
+
+/// Children accepted by the argument-taking html constructors (e.g.
+/// `html::div_with`)
+///
+/// This is implemented for any type that implements `View<Node<Msg>>` (e.g.
+/// `&'static str` and `String`), nodes, and the following containers of
+/// children:
+/// - tuples (up to 12 items) of different children types
+/// - `Vec`, arrays and `Option`
+/// - iterators created by `Iterator::map`
+///
+/// Other iterators (e.g. `filter`, `chain` or `map` followed by another
+/// adaptor) can be passed by wrapping them with [`children_iter`].
+///
+/// ```rust
+/// # use savory::prelude::*;
+/// let fruits = vec!["Apple", "Orange"];
+/// let node: Node<()> = html::div_with((
+///     html::h1_with("Fruits"),
+///     html::ul_with(fruits.into_iter().map(html::li_with)),
+///     html::p_with(("There are ", 2.to_string(), " fruits")),
+/// ));
+///
+/// assert_eq!(
+///     node.to_html(),
+///     "<div><h1>Fruits</h1><ul><li>Apple</li><li>Orange</li></ul><p>There are 2 fruits</p></div>"
+/// );
+/// ```
+pub trait Children<Msg> {
+    fn into_children(self) -> Vec<Node<Msg>>;
+}
+
+impl<Msg, T> Children<Msg> for T
+where
+    T: View<Node<Msg>>,
+{
+    fn into_children(self) -> Vec<Node<Msg>> {
+        vec![self.view()]
+    }
+}
+
+impl<Msg> Children<Msg> for Node<Msg> {
+    fn into_children(self) -> Vec<Node<Msg>> {
+        vec![self]
+    }
+}
+
+impl<Msg> Children<Msg> for El<Msg> {
+    fn into_children(self) -> Vec<Node<Msg>> {
+        vec![Node::Element(self)]
+    }
+}
+
+impl<T, Msg> Children<Msg> for HtmlEl<T, Msg> {
+    fn into_children(self) -> Vec<Node<Msg>> {
+        vec![self.into_node()]
+    }
+}
+
+impl<Msg, T> Children<Msg> for Vec<T>
+where
+    T: Children<Msg>,
+{
+    fn into_children(self) -> Vec<Node<Msg>> {
+        self.into_iter().flat_map(Children::into_children).collect()
+    }
+}
+
+impl<Msg, T, const N: usize> Children<Msg> for [T; N]
+where
+    T: Children<Msg>,
+{
+    fn into_children(self) -> Vec<Node<Msg>> {
+        IntoIterator::into_iter(self)
+            .flat_map(Children::into_children)
+            .collect()
+    }
+}
+
+impl<Msg, T> Children<Msg> for Option<T>
+where
+    T: Children<Msg>,
+{
+    fn into_children(self) -> Vec<Node<Msg>> {
+        self.map(Children::into_children).unwrap_or_default()
+    }
+}
+
+impl<Msg, I, F, T> Children<Msg> for std::iter::Map<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> T,
+    T: Children<Msg>,
+{
+    fn into_children(self) -> Vec<Node<Msg>> {
+        self.flat_map(Children::into_children).collect()
+    }
+}
+
+/// Wrap any iterator of children so it can be passed as children
+///
+/// ```rust
+/// # use savory::prelude::*;
+/// let fruits = vec!["Apple", "Orange", "Banana"];
+/// let node: Node<()> = html::ul_with(html::children_iter(
+///     fruits
+///         .into_iter()
+///         .filter(|fruit| fruit.starts_with('A') || fruit.starts_with('B'))
+///         .map(html::li_with)
+///         .take(2),
+/// ));
+///
+/// assert_eq!(node.to_html(), "<ul><li>Apple</li><li>Banana</li></ul>");
+/// ```
+pub fn children_iter<I>(iter: I) -> ChildrenIter<I::IntoIter>
+where
+    I: IntoIterator,
+{
+    ChildrenIter(iter.into_iter())
+}
+
+/// Iterator of children, see [`children_iter`]
+pub struct ChildrenIter<I>(I);
+
+impl<Msg, I> Children<Msg> for ChildrenIter<I>
+where
+    I: Iterator,
+    I::Item: Children<Msg>,
+{
+    fn into_children(self) -> Vec<Node<Msg>> {
+        self.0.flat_map(Children::into_children).collect()
+    }
+}
+
+macro_rules! impl_children_for_tuples {
+    ( $( ( $( $ty:ident ),* ) )* ) => {
+        $(
+            impl<Msg, $( $ty ),*> Children<Msg> for ( $( $ty, )* )
+            where
+                $( $ty: Children<Msg>, )*
+            {
+                #[allow(non_snake_case)]
+                fn into_children(self) -> Vec<Node<Msg>> {
+                    let ( $( $ty, )* ) = self;
+                    let mut children = vec![];
+                    $( children.extend($ty.into_children()); )*
+                    children
+                }
+            }
+        )*
+    };
+}
+
+impl_children_for_tuples! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
@@ -0,0 +1,129 @@
+use crate::prelude::*;
+use std::marker::PhantomData;
+
+/// HTML element created by typed constructors
+///
+/// `T` is a marker type from [`tags`](super::tags) that decides which
+/// attribute methods are available on the element, global attributes are
+/// available for all elements through `AttributeApi`.
+///
+/// `HtmlEl` can be pushed into other nodes as is, use `into()` to convert it
+/// into `Node` when needed.
+pub struct HtmlEl<T, Msg> {
+    el: El<Msg>,
+    tag: PhantomData<T>,
+}
+
+impl<T, Msg> HtmlEl<T, Msg> {
+    pub(crate) fn new(node: Node<Msg>) -> Self {
+        match node {
+            Node::Element(el) => Self {
+                el,
+                tag: PhantomData,
+            },
+            _ => unreachable!("html constructors create elements"),
+        }
+    }
+
+    /// The underlying element
+    pub fn el(&self) -> &El<Msg> {
+        &self.el
+    }
+
+    pub fn into_el(self) -> El<Msg> {
+        self.el
+    }
+
+    pub fn into_node(self) -> Node<Msg> {
+        Node::Element(self.el)
+    }
+}
+
+impl<T, Msg> From<HtmlEl<T, Msg>> for Node<Msg> {
+    fn from(source: HtmlEl<T, Msg>) -> Self {
+        source.into_node()
+    }
+}
+
+impl<T, Msg> From<HtmlEl<T, Msg>> for El<Msg> {
+    fn from(source: HtmlEl<T, Msg>) -> Self {
+        source.el
+    }
+}
+
+impl<T, Msg> AttributeApi for HtmlEl<T, Msg> {
+    fn set_attr(self, attr: impl ToString, val: impl ToString) -> Self {
+        self.and_el(|el| el.set_attr(attr, val))
+    }
+
+    fn get_attr(&self, attr: impl ToString) -> Option<&str> {
+        self.el.get_attr(attr)
+    }
+
+    fn remove_attr(self, attr: impl ToString) -> Self {
+        self.and_el(|el| el.remove_attr(attr))
+    }
+}
+
+impl<T, Msg> AndEl<Msg> for HtmlEl<T, Msg> {
+    fn and_el(mut self, conf: impl FnOnce(El<Msg>) -> El<Msg>) -> Self {
+        self.el = conf(self.el);
+        self
+    }
+}
+
+impl<T, Msg, V> PushOwned<V> for HtmlEl<T, Msg>
+where
+    El<Msg>: PushOwned<V>,
+{
+    fn push(self, val: V) -> Self {
+        self.and_el(|el| el.push(val))
+    }
+}
+
+impl<T, Msg, V> SetOwned<V> for HtmlEl<T, Msg>
+where
+    El<Msg>: SetOwned<V>,
+{
+    fn set(self, val: V) -> Self {
+        self.and_el(|el| el.set(val))
+    }
+}
+
+impl<T, Msg> PushOwned<HtmlEl<T, Msg>> for El<Msg> {
+    fn push(self, val: HtmlEl<T, Msg>) -> Self {
+        self.push(val.into_node())
+    }
+}
+
+impl<T, Msg> PushOwned<HtmlEl<T, Msg>> for Node<Msg> {
+    fn push(self, val: HtmlEl<T, Msg>) -> Self {
+        self.push(val.into_node())
+    }
+}
+
+impl<T, Msg> SetOwned<HtmlEl<T, Msg>> for El<Msg> {
+    fn set(self, val: HtmlEl<T, Msg>) -> Self {
+        self.set(val.into_node())
+    }
+}
+
+impl<T, Msg> SetOwned<HtmlEl<T, Msg>> for Node<Msg> {
+    fn set(self, val: HtmlEl<T, Msg>) -> Self {
+        self.set(val.into_node())
+    }
+}
+
+impl<T, Msg> ElRefExt for HtmlEl<T, Msg> {
+    fn el_ref<E: Clone>(self, reference: &ElRef<E>) -> Self {
+        self.and_el(|el| el.el_ref(reference))
+    }
+}
+
+impl<T, Msg> DeclarativeConfig for HtmlEl<T, Msg> {}
+
+impl<T, Msg> ToHtml for HtmlEl<T, Msg> {
+    fn write_html(&self, out: &mut String) {
+        self.el.write_html(out)
+    }
+}
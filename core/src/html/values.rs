@@ -0,0 +1,191 @@
+//! Typed values for enumerated HTML attributes
+//!
+//! Values are parsed ASCII case-insensitively as HTML does, e.g. `TEXT` is
+//! parsed as `InputType::Text`.
+
+use std::{fmt, str::FromStr};
+
+/// Error returned when parsing invalid attribute value
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttrValueError {
+    pub value: String,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for AttrValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value `{}` for {}", self.value, self.expected)
+    }
+}
+
+impl std::error::Error for AttrValueError {}
+
+macro_rules! attr_values {
+    ( $( $(#[$doc:meta])* $name:ident { $( $variant:ident = $val:literal $(,)? )* } )* ) => {
+        $(
+            $( #[$doc] )*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $( $variant, )*
+            }
+
+            impl $name {
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $( Self::$variant => $val, )*
+                    }
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl FromStr for $name {
+                type Err = AttrValueError;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    match value {
+                        $( _ if value.eq_ignore_ascii_case($val) => Ok(Self::$variant), )*
+                        _ => Err(AttrValueError {
+                            value: value.to_string(),
+                            expected: stringify!($name),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use attr_values;
+
+attr_values! {
+    /// Value of `type` attribute for `input` elements
+    InputType {
+        Button = "button",
+        Checkbox = "checkbox",
+        Color = "color",
+        Date = "date",
+        DateTimeLocal = "datetime-local",
+        Email = "email",
+        File = "file",
+        Hidden = "hidden",
+        Image = "image",
+        Month = "month",
+        Number = "number",
+        Password = "password",
+        Radio = "radio",
+        Range = "range",
+        Reset = "reset",
+        Search = "search",
+        Submit = "submit",
+        Tel = "tel",
+        Text = "text",
+        Time = "time",
+        Url = "url",
+        Week = "week",
+    }
+
+    /// Value of `type` attribute for `button` elements
+    ButtonType {
+        Submit = "submit",
+        Reset = "reset",
+        Button = "button",
+    }
+
+    /// Browsing context used by links and forms, use `set_attr` for named
+    /// browsing contexts
+    Target {
+        Self_ = "_self",
+        Blank = "_blank",
+        Parent = "_parent",
+        Top = "_top",
+    }
+
+    /// Value of `loading` attribute for `img` and `iframe` elements
+    Loading {
+        Eager = "eager",
+        Lazy = "lazy",
+    }
+
+    /// Value of `decoding` attribute for `img` elements
+    Decoding {
+        Sync = "sync",
+        Async = "async",
+        Auto = "auto",
+    }
+
+    /// HTTP method used to submit forms
+    FormMethod {
+        Get = "get",
+        Post = "post",
+        Dialog = "dialog",
+    }
+
+    /// Encoding type used to submit forms
+    EncType {
+        UrlEncoded = "application/x-www-form-urlencoded",
+        MultipartFormData = "multipart/form-data",
+        TextPlain = "text/plain",
+    }
+
+    /// Value of `crossorigin` attribute
+    CrossOrigin {
+        Anonymous = "anonymous",
+        UseCredentials = "use-credentials",
+    }
+
+    /// Value of `referrerpolicy` attribute
+    ReferrerPolicy {
+        NoReferrer = "no-referrer",
+        NoReferrerWhenDowngrade = "no-referrer-when-downgrade",
+        Origin = "origin",
+        OriginWhenCrossOrigin = "origin-when-cross-origin",
+        SameOrigin = "same-origin",
+        StrictOrigin = "strict-origin",
+        StrictOriginWhenCrossOrigin = "strict-origin-when-cross-origin",
+        UnsafeUrl = "unsafe-url",
+    }
+
+    /// Value of `preload` attribute for `audio` and `video` elements
+    Preload {
+        None = "none",
+        Metadata = "metadata",
+        Auto = "auto",
+    }
+
+    /// Value of `wrap` attribute for `textarea` elements
+    Wrap {
+        Hard = "hard",
+        Soft = "soft",
+    }
+
+    /// Value of `kind` attribute for `track` elements
+    TrackKind {
+        Subtitles = "subtitles",
+        Captions = "captions",
+        Descriptions = "descriptions",
+        Chapters = "chapters",
+        Metadata = "metadata",
+    }
+
+    /// Value of `scope` attribute for `th` elements
+    Scope {
+        Row = "row",
+        Col = "col",
+        RowGroup = "rowgroup",
+        ColGroup = "colgroup",
+    }
+
+    /// Value of `type` attribute for `ol` elements
+    ListType {
+        Decimal = "1",
+        LowerAlpha = "a",
+        UpperAlpha = "A",
+        LowerRoman = "i",
+        UpperRoman = "I",
+    }
+}
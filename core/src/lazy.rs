@@ -0,0 +1,181 @@
+//! Memoized views.
+//!
+//! Views are rebuilt on every render, which can be slow for big subtrees.
+//! [`Lazy`] remembers the dependencies of a view, and skips the view as long as
+//! the dependencies are equal to the ones used to build it:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! struct Report {
+//!     rows: Vec<(String, u32)>,
+//!     table: Lazy<Vec<(String, u32)>, ()>,
+//! }
+//!
+//! impl View<Node<()>> for Report {
+//!     fn view(&self) -> Node<()> {
+//!         // the table is only rebuilt when `rows` change
+//!         self.table.memo(self.rows.clone(), || {
+//!             html::table().push_iter(self.rows.iter().map(|(name, count)| {
+//!                 html::tr_with((html::td_with(name.clone()), html::td_with(count.to_string())))
+//!             }))
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! When the dependencies didn't change, `memo` returns `Node::NoChange`, which
+//! tells Seed to keep the node it rendered at the same position in the last
+//! render, so the subtree is neither rebuilt nor diffed. This only works when
+//! the node returned by `memo` is rendered at a stable position:
+//!
+//! - every node returned by `memo` must be rendered, a node that is discarded
+//!   leaves Seed with a different node at that position
+//! - the node must stay at the same position between renders, e.g. it must not
+//!   be conditionally preceded by other nodes in the same parent, or be an
+//!   item in a list that get reordered
+//!
+//! Use [`Lazy::clear`] to rebuild the node after breaking these rules, or use
+//! [`Lazy::memo_cloned`] for nodes that don't have a stable position, which
+//! returns a clone of the cached node, this skips building the subtree but
+//! Seed still diffs it against the rendered DOM.
+
+use crate::prelude::*;
+use std::{cell::RefCell, fmt};
+
+/// Cache for the dependencies of a view
+///
+/// See [module docs](crate::lazy) for more details.
+pub struct Lazy<D, Msg> {
+    // the node is cached by `memo_cloned` only
+    cache: RefCell<Option<(D, Option<Node<Msg>>)>>,
+}
+
+impl<D, Msg> Lazy<D, Msg> {
+    pub fn new() -> Self {
+        Self {
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Drop the cached node, so the next `memo` call rebuilds it
+    pub fn clear(&self) {
+        self.cache.replace(None);
+    }
+}
+
+impl<D: PartialEq, Msg> Lazy<D, Msg> {
+    /// Return `Node::NoChange` if `deps` equals the dependencies of the last
+    /// built node, otherwise build the node using `view`
+    ///
+    /// The returned node must be rendered at a stable position, see [module
+    /// docs](crate::lazy) for more details.
+    pub fn memo<V>(&self, deps: D, view: impl FnOnce() -> V) -> Node<Msg>
+    where
+        V: Into<Node<Msg>>,
+    {
+        let mut cache = self.cache.borrow_mut();
+        match *cache {
+            Some((ref cached_deps, _)) if *cached_deps == deps => Node::NoChange,
+            _ => {
+                *cache = Some((deps, None));
+                view().into()
+            }
+        }
+    }
+
+    /// Return a clone of the cached node if `deps` equals the dependencies of
+    /// the cached node, otherwise build the node using `view` and cache it
+    ///
+    /// Unlike [`memo`](Lazy::memo), the returned node can be rendered at any
+    /// position or discarded.
+    pub fn memo_cloned<V>(&self, deps: D, view: impl FnOnce() -> V) -> Node<Msg>
+    where
+        V: Into<Node<Msg>>,
+    {
+        let mut cache = self.cache.borrow_mut();
+        match *cache {
+            Some((ref cached_deps, Some(ref node))) if *cached_deps == deps => node.clone(),
+            _ => {
+                let node = view().into();
+                *cache = Some((deps, Some(node.clone())));
+                node
+            }
+        }
+    }
+}
+
+impl<D, Msg> Default for Lazy<D, Msg> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: fmt::Debug, Msg> fmt::Debug for Lazy<D, Msg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("deps", &self.cache.borrow().as_ref().map(|(deps, _)| deps))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_memo() {
+        let lazy = Lazy::<u32, ()>::new();
+        let builds = Cell::new(0);
+        let view = |count: u32| {
+            builds.set(builds.get() + 1);
+            html::span().push(count.to_string())
+        };
+
+        assert_eq!(lazy.memo(1, || view(1)).to_html(), "<span>1</span>");
+        assert!(matches!(lazy.memo(1, || view(1)), Node::NoChange));
+        assert_eq!(builds.get(), 1);
+
+        assert_eq!(lazy.memo(2, || view(2)).to_html(), "<span>2</span>");
+        assert_eq!(builds.get(), 2);
+
+        lazy.clear();
+        assert_eq!(lazy.memo(2, || view(2)).to_html(), "<span>2</span>");
+        assert_eq!(builds.get(), 3);
+    }
+
+    #[test]
+    fn test_memo_cloned() {
+        let lazy = Lazy::<&str, ()>::new();
+        let builds = Cell::new(0);
+        let view = |text: &str| {
+            builds.set(builds.get() + 1);
+            html::span().push(text.to_string())
+        };
+
+        // the first view is thrown away before it's rendered, the next views
+        // still return the whole node
+        let _ = lazy.memo_cloned("a", || view("a"));
+        assert_eq!(
+            lazy.memo_cloned("a", || view("a")).to_html(),
+            "<span>a</span>"
+        );
+        assert_eq!(
+            lazy.memo_cloned("a", || view("a")).to_html(),
+            "<span>a</span>"
+        );
+        assert_eq!(builds.get(), 1);
+
+        // `memo` doesn't cache the node, so it's rebuilt once
+        let _ = lazy.memo("b", || view("b"));
+        assert_eq!(
+            lazy.memo_cloned("b", || view("b")).to_html(),
+            "<span>b</span>"
+        );
+        assert_eq!(
+            lazy.memo_cloned("b", || view("b")).to_html(),
+            "<span>b</span>"
+        );
+        assert_eq!(builds.get(), 3);
+    }
+}
@@ -108,12 +108,23 @@
 #[macro_use]
 pub extern crate seed;
 
+pub mod aria;
+pub mod classes;
 pub mod element;
+pub mod element_list;
 pub mod env;
 pub mod events;
 pub mod html;
+pub mod lazy;
 pub mod node;
 pub mod orders;
+pub mod portal;
+pub mod query;
+pub mod render;
+pub mod store;
+pub mod svg;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod traits;
 pub mod view;
 
@@ -121,14 +132,22 @@ pub use web_sys;
 
 pub mod prelude {
     pub use crate::{
-        element::{AppElementExt, Element},
-        env::Env,
+        aria::{AriaApi, Live, Role, Tristate},
+        classes::{classes, Classes},
+        element::{AppElementExt, Element, ElementExt, Lifecycle},
+        element_list::{self, ElementList},
+        env::{Env, EnvKey},
         events::*,
         html,
+        lazy::Lazy,
         node::*,
         orders::*,
+        query::QueryApi,
+        render::ToHtml,
         seed::prelude::{subs, wasm_bindgen, web_sys, ElRef, MessageMapper, Url},
+        store::{Reducer, Store},
+        svg::PresentationApi,
         traits::*,
-        view::View,
+        view::{Either, View},
     };
 }
@@ -1,9 +1,12 @@
 //! Traits and implementation that makes working with `Node` API declarative and
 //! convenient.
 
-use crate::prelude::*;
+use crate::{classes::Classes, html::AttrValueError, prelude::*};
 use paste::paste;
-use seed::prelude::{At, AtValue};
+use seed::{
+    prelude::{At, AtValue},
+    virtual_dom::el_key,
+};
 
 pub use seed::prelude::{El, Node};
 
@@ -216,23 +219,15 @@ macro_rules! attr_fns {
             }
         )*
     };
-    (@match_like $ty:ident no_impl $( $attr_name:literal $attr_ident:ident )*) => {
+    (@match_like bool presence $( $attr_name:literal $attr_ident:ident )*) => {
         $(
-            fn $attr_ident(self, val: $ty) -> Self;
+            fn $attr_ident(self, val: bool) -> Self {
+                self.toggle_attr($attr_name, val)
+            }
 
             paste! {
-                fn [<try_ $attr_ident>](self, val: Option<$ty>) -> Self {
-                    match val {
-                        Some(val) => self.$attr_ident(val),
-                        None => self,
-                    }
-                }
-
-                fn [<get_ $attr_ident>](&self) -> Option<$ty> {
-                    match self.get_attr($attr_name) {
-                        Some(val) => Some(val.parse::<$ty>().expect(&format!("expect value of type {} for attribute {}", stringify!($ty), $attr_name))),
-                        None => None,
-                    }
+                fn [<get_ $attr_ident>](&self) -> bool {
+                    self.has_attr($attr_name)
                 }
             }
         )*
@@ -251,11 +246,13 @@ macro_rules! attr_fns {
                     }
                 }
 
-                fn [<get_ $attr_ident>](&self) -> Option<$ty> {
-                    match self.get_attr($attr_name) {
-                        Some(val) => Some(val.parse::<$ty>().expect(&format!("expect value of type {} for attribute {}", stringify!($ty), $attr_name))),
-                        None => None,
-                    }
+                fn [<get_ $attr_ident>](&self) -> Option<Result<$ty, AttrValueError>> {
+                    self.get_attr($attr_name).map(|val| {
+                        val.parse::<$ty>().map_err(|_| AttrValueError {
+                            value: val.to_string(),
+                            expected: stringify!($ty),
+                        })
+                    })
                 }
             }
         )*
@@ -269,17 +266,119 @@ macro_rules! attr_fns {
     };
 }
 
+/// Global attributes that are available for all elements
+///
+/// Attributes that belong to specific elements are provided by
+/// [`UntypedAttributeApi`] and [`HtmlEl`](crate::html::HtmlEl).
 pub trait AttributeApi: Sized {
     /// set an attribute with it's value
     fn set_attr(self, attr: impl ToString, val: impl ToString) -> Self;
     /// get an attribute value if it exist
     fn get_attr(&self, attr: impl ToString) -> Option<&str>;
+    /// remove an attribute if it exist
+    fn remove_attr(self, attr: impl ToString) -> Self;
+
+    /// check if an attribute exist
+    fn has_attr(&self, attr: impl ToString) -> bool {
+        self.get_attr(attr).is_some()
+    }
+
+    /// set boolean attribute (e.g. `hidden`), boolean attributes are true when
+    /// they exist, so they get removed when `val` is `false`
+    fn toggle_attr(self, attr: impl ToString, val: bool) -> Self {
+        match val {
+            true => self.set_attr(attr, ""),
+            false => self.remove_attr(attr),
+        }
+    }
+
+    /// add classes to the existing classes, see [`Classes`](crate::classes::Classes)
+    fn classes(self, classes: impl Into<Classes>) -> Self {
+        let mut merged = Classes::from(self.get_class().unwrap_or_default());
+        merged.extend(classes.into().iter());
+        match merged.is_empty() {
+            true => self,
+            false => self.class(merged),
+        }
+    }
+
+    /// add class, or multiple classes separated by whitespace
+    fn add_class(self, class: impl AsRef<str>) -> Self {
+        self.classes(Classes::new().add(class))
+    }
+
+    /// remove class if it exist, the `class` attribute get removed when it
+    /// becomes empty
+    fn remove_class(self, class: &str) -> Self {
+        let classes = Classes::from(self.get_class().unwrap_or_default()).remove(class);
+        match classes.is_empty() {
+            true => self.remove_attr("class"),
+            false => self.class(classes),
+        }
+    }
+
+    /// add class when `val` is `true`, remove it otherwise
+    fn toggle_class(self, class: &str, val: bool) -> Self {
+        match val {
+            true => self.add_class(class),
+            false => self.remove_class(class),
+        }
+    }
+
+    /// check if the element have `class`
+    fn has_class(&self, class: &str) -> bool {
+        self.get_class()
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    }
 
     // TODO: add docs
     attr_fns!(
         String {
             "class": class,
             "id": id,
+            "accesskey": access_key,
+            "autocapitalize": auto_capitalize,
+            "dir": dir,
+            "enterkeyhint": enter_key_hint,
+            "inputmode": input_mode,
+            "is": is,
+            "itemid": item_id,
+            "itemprop": item_prop,
+            "itemref": item_ref,
+            "itemtype": item_type,
+            "lang": lang,
+            "nonce": nonce,
+            "slot": slot,
+            "title": title,
+            "translate": translate,
+        }
+        bool {
+            "contenteditable": content_editable,
+            "draggable": draggable,
+            "spellcheck": spell_check,
+        }
+        bool (presence) {
+            "autofocus": auto_focus,
+            "hidden": hidden,
+            "itemscope": item_scope,
+        }
+        i32 {
+            "tabindex": tab_index,
+        }
+    );
+}
+
+/// Attributes that belong to specific elements, for nodes created by the
+/// plain constructors (e.g. `html::input()`) or by `html::custom`
+///
+/// This is implemented for `Node` and `El` only, elements created by the
+/// constructors in [`html::typed`](crate::html::typed) provide the attributes
+/// that belong to them instead.
+#[allow(clippy::wrong_self_convention)]
+pub trait UntypedAttributeApi: AttributeApi {
+    // TODO: add docs
+    attr_fns!(
+        String {
             "abbr": abbr,
             "accept": accept,
             "accept-charset": accept_charset,
@@ -292,56 +391,35 @@ pub trait AttributeApi: Sized {
             "color": color,
             "content": content,
             "data": data,
-            "date-time": datetime,
-            "dir-name": dir_name,
+            "datetime": datetime,
+            "dirname": dir_name,
             "download": download,
             "for": for_,
             "form": form,
-            "form-action": form_action,
+            "formaction": form_action,
             "formtarget": form_target,
             "headers": headers,
             "href": href,
-            "href-lang": href_lang,
+            "hreflang": href_lang,
             "integrity": integrity,
-            "is": is,
-            "itemid": item_id,
             "itemgroup": item_group,
-            "itemref": item_ref,
-            "itemtype": item_type,
             "label": label,
-            "lang": lang,
             "list": list,
             "manifest": manifest,
             "media": media,
             "name": name,
-            "nonce": nonce,
             "placeholder": placeholder,
             "poster": poster,
-            "slot": slot,
             "src": src,
-            "src-doc": src_doc,
-            "src-lang": src_lang,
-            "src-set": src_set,
-            // this need custom implementation that is found in savory-style
-            // "style": style,
-            "title": title,
-            "use-map": use_map,
+            "srcdoc": src_doc,
+            "srclang": src_lang,
+            "srcset": src_set,
+            "usemap": use_map,
             "value": value,
-            // the following can be enum instead of strings
-            // Note: some attributes for SVG nodes only, these might go to separate trait
             "step": step,
             "target": target,
-            "translate": translate,
             "type": type_,
             "wrap": wrap,
-            "cx": cx,
-            "cy": cy,
-            "r": r,
-            "rx": rx,
-            "ry": ry,
-            "x": x,
-            "y": y,
-            "view-box": view_box,
             "max": max,
             "method": method,
             "min": min,
@@ -353,59 +431,58 @@ pub trait AttributeApi: Sized {
             "scope": scope,
             "shape": shape,
             "kind": kind,
-            "draggable": draggable,
-            "enc-type": enc_type,
-            "entry_key_hint": enter_key_hint,
-            "fromenctype": form_enc_type,
-            "frommethod": form_method,
-            "inputmode": input_mode,
+            "enctype": enc_type,
+            "formenctype": form_enc_type,
+            "formmethod": form_method,
             "decoding": decoding,
             "loading": loading,
-            "dir": dir,
             "coords": coords,
             "crossorigin": cross_origin,
-            "autocapitalize": auto_capitalize,
-            "auto-complete": auto_complete,
+            "autocomplete": auto_complete,
+            // SVG attributes
+            "x": x,
+            "y": y,
+            "cx": cx,
+            "cy": cy,
+            "r": r,
+            "rx": rx,
+            "ry": ry,
+            "viewBox": view_box,
         }
-        bool (no_impl) {
+        bool (presence) {
             "disabled": disabled,
-        }
-        bool {
             "allowfullscreen": allow_full_screen,
             "allowpaymentrequest": allow_payment_request,
-            "auto-focus": auto_focus,
-            "auto-play": auto_play,
+            "autoplay": auto_play,
             "checked": checked,
-            "content-editable": content_editable,
             "controls": controls,
             "default": default,
             "defer": defer,
             "formnovalidate": form_no_validate,
-            "hidden": hidden,
-            "is-map": is_map,
-            "itemscope": item_scope,
+            "ismap": is_map,
             "loop": loop_,
             "multiple": multiple,
             "muted": muted,
             "nomodule": no_module,
-            "no-validate": no_validate,
+            "novalidate": no_validate,
             "open": open,
             "playsinline": plays_in_line,
-            "read-only": read_only,
+            "readonly": read_only,
             "required": required,
             "reversed": reversed,
             "selected": selected,
-            "spell-check": spell_check,
         }
         u32 {
             "cols": cols,
-            "col-span": col_span,
+            "colspan": col_span,
             "height": height,
             "rows": rows,
-            "row-span": row_span,
+            "rowspan": row_span,
             "size": size,
             "span": span,
             "width": width,
+            "maxlength": max_length,
+            "minlength": min_length,
         }
         f32 {
             "high": high,
@@ -413,14 +490,15 @@ pub trait AttributeApi: Sized {
             "optimum": optimum,
         }
         i32 {
-            "max-length": max_length,
-            "min-length": min_length,
-            "tab-index": tab_index,
             "start": start,
         }
     );
 }
 
+impl<Msg> UntypedAttributeApi for El<Msg> {}
+
+impl<Msg> UntypedAttributeApi for Node<Msg> {}
+
 impl<Msg> AttributeApi for El<Msg> {
     fn set_attr(mut self, attr: impl ToString, val: impl ToString) -> Self {
         self.attrs
@@ -436,14 +514,9 @@ impl<Msg> AttributeApi for El<Msg> {
         }
     }
 
-    fn disabled(mut self, val: bool) -> Self {
-        match val {
-            true => self.set_attr(At::Disabled, val),
-            false => {
-                self.attrs.vals.remove(&At::Disabled);
-                self
-            }
-        }
+    fn remove_attr(mut self, attr: impl ToString) -> Self {
+        self.attrs.vals.remove(&At::from(attr.to_string()));
+        self
     }
 }
 
@@ -459,8 +532,8 @@ impl<Msg> AttributeApi for Node<Msg> {
         }
     }
 
-    fn disabled(self, val: bool) -> Self {
-        self.and_el(|el| el.disabled(val))
+    fn remove_attr(self, attr: impl ToString) -> Self {
+        self.and_el(|el| el.remove_attr(attr))
     }
 }
 
@@ -479,6 +552,73 @@ impl<Msg> AndEl<Msg> for Node<Msg> {
     }
 }
 
+impl<Msg> AndEl<Msg> for El<Msg> {
+    fn and_el(self, conf: impl FnOnce(El<Msg>) -> El<Msg>) -> Self {
+        conf(self)
+    }
+}
+
+/// Push list of views with stable keys
+pub trait PushKeyedApi<Msg>: AndEl<Msg> + Sized {
+    /// Push the views returned by `view_fn` for every item in `iter`, the view
+    /// key is set to the key returned by `key_fn`
+    ///
+    /// Keys help Seed's diffing to match the old views with the new views when
+    /// items get added, removed or reordered, so the DOM state of the items
+    /// (e.g. focus and scroll position) is kept. Keys are only set on element
+    /// nodes, and they must be unique within the same parent.
+    ///
+    /// ```rust
+    /// # use savory::prelude::*;
+    /// # use seed::virtual_dom::el_key;
+    /// struct Todo {
+    ///     id: u32,
+    ///     text: &'static str,
+    /// }
+    ///
+    /// impl View<Node<()>> for Todo {
+    ///     fn view(&self) -> Node<()> {
+    ///         html::li().push(self.text)
+    ///     }
+    /// }
+    ///
+    /// let todos = vec![Todo { id: 4, text: "Write docs" }, Todo { id: 7, text: "Ship it" }];
+    /// let node: Node<()> = html::ul()
+    ///     .push_keyed_iter(todos, |todo| todo.id, |todo| todo);
+    ///
+    /// assert_eq!(node.to_html(), "<ul><li>Write docs</li><li>Ship it</li></ul>");
+    /// if let Node::Element(el) = node {
+    ///     let keys = el.children.iter().map(|node| node.el_key().cloned());
+    ///     assert_eq!(keys.collect::<Vec<_>>(), vec![Some(el_key(&4)), Some(el_key(&7))]);
+    /// }
+    /// ```
+    fn push_keyed_iter<I, K, V>(
+        self,
+        iter: I,
+        key_fn: impl Fn(&I::Item) -> K,
+        view_fn: impl Fn(I::Item) -> V,
+    ) -> Self
+    where
+        I: IntoIterator,
+        K: ToString,
+        V: View<Node<Msg>>,
+    {
+        self.and_el(|mut el| {
+            el.children.extend(iter.into_iter().map(|item| {
+                let key = key_fn(&item);
+                let mut node = view_fn(item).view();
+                if let Node::Element(ref mut el) = node {
+                    el.key = Some(el_key(&key));
+                }
+                node
+            }));
+            el
+        })
+    }
+}
+
+impl<Msg, T> PushKeyedApi<Msg> for T where T: AndEl<Msg> {}
+
 /// Helper triat used to make working with `ElRef` convenient.
 pub trait ElRefExt {
     // TODO: add docs
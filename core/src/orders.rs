@@ -1,9 +1,229 @@
-//! Traits extensions that makes working with `Orders` API more convenient.
+//! Orders used by elements to interact with Seed runtime.
+//!
+//! [`Orders`] has the same methods as Seed's `Orders`, it's implemented for
+//! Seed's orders (so it works with any Seed app) and for the orders used by
+//! `testing::Harness`. Unlike Seed's `Orders`, `proxy` and the `*_with_handle`
+//! methods don't need a running app, `proxy` returns orders that map child
+//! messages into the parent messages, and the `*_with_handle` methods return
+//! [`TaskHandle`].
+//!
+//! [`OrdersExt`] provides more convenient methods on top of [`Orders`].
 
-use seed::prelude::{CmdHandle, StreamHandle};
-use std::future::Future;
+use crate::{
+    env::{Env, EnvKey},
+    events::{shortcuts, KeyCombo},
+    store::{Reducer, Store},
+};
+use seed::{
+    app::{OrdersContainer, OrdersProxy, RenderInfo},
+    futures::{future, stream, FutureExt, Stream, StreamExt},
+    prelude::{subs, IntoNodes, Url},
+};
+use std::{any::Any, fmt, future::Future, pin::Pin, rc::Rc};
 
-pub use seed::prelude::{cmds, streams, Orders};
+pub use seed::prelude::{cmds, streams};
+
+/// Orders used by elements to interact with Seed runtime
+///
+/// Handlers passed to `perform_cmd`, `after_next_render`, `subscribe` and
+/// `stream` (and their variants) can return `Ms`, `Option<Ms>` or `()`, the
+/// same as in Seed.
+///
+/// See [module docs](crate::orders) for more details.
+pub trait Orders<Ms: 'static> {
+    /// Create orders for a child element, messages sent by the child are
+    /// mapped into parent messages using `f`
+    ///
+    /// # Example
+    /// ```
+    /// # use savory::prelude::*;
+    /// # struct Counter;
+    /// # impl Element for Counter {
+    /// #     type Message = ();
+    /// #     type Config = ();
+    /// #     fn init(_: (), _: &mut impl Orders<()>, _: Env) -> Self { Counter }
+    /// #     fn update(&mut self, _: (), _: &mut impl Orders<()>) {}
+    /// # }
+    /// enum Msg {
+    ///     Counter(()),
+    /// }
+    ///
+    /// fn init(orders: &mut impl Orders<Msg>, env: Env) -> Counter {
+    ///     Counter::init((), &mut orders.proxy(Msg::Counter), env)
+    /// }
+    /// ```
+    fn proxy<ChildMs: 'static>(
+        &mut self,
+        f: impl FnOnce(ChildMs) -> Ms + Clone + 'static,
+    ) -> MappedOrders<'_, Self, Ms, ChildMs> {
+        MappedOrders::new(self, f)
+    }
+
+    /// Schedule rerender after the update, this is the default behavior
+    fn render(&mut self) -> &mut Self;
+
+    /// Rerender immediately after the update
+    fn force_render_now(&mut self) -> &mut Self;
+
+    /// Don't rerender after the update
+    fn skip(&mut self) -> &mut Self;
+
+    /// Send `message` to all subscriptions of its type
+    fn notify(&mut self, message: impl Any + Clone) -> &mut Self;
+
+    /// Send `msg` to the element
+    fn send_msg(&mut self, msg: Ms) -> &mut Self;
+
+    /// Run `cmd` and send the message it produces
+    fn perform_cmd<MsU: 'static>(&mut self, cmd: impl Future<Output = MsU> + 'static) -> &mut Self;
+
+    /// Same as `perform_cmd` but the command is aborted once the returned
+    /// handle is dropped
+    #[must_use = "cmd is aborted on its handle drop"]
+    fn perform_cmd_with_handle<MsU: 'static>(
+        &mut self,
+        cmd: impl Future<Output = MsU> + 'static,
+    ) -> TaskHandle {
+        let (cmd, handle) = future::abortable(cmd);
+        self.perform_cmd(cmd.map(|msg| msg.ok().and_then(into_msg::<Ms, MsU>)));
+        TaskHandle::new(move || handle.abort())
+    }
+
+    /// Function that sends messages to the element, useful to send messages
+    /// from callbacks (e.g. JS callbacks)
+    fn msg_sender(&self) -> Rc<dyn Fn(Option<Ms>)>;
+
+    /// Call `callback` after the next render
+    fn after_next_render<MsU: 'static>(
+        &mut self,
+        callback: impl FnOnce(RenderInfo) -> MsU + 'static,
+    ) -> &mut Self;
+
+    /// Call `handler` with every notification of type `SubMs`
+    fn subscribe<MsU: 'static, SubMs: 'static + Clone>(
+        &mut self,
+        handler: impl FnOnce(SubMs) -> MsU + Clone + 'static,
+    ) -> &mut Self;
+
+    /// Same as `subscribe` but the subscription is removed once the returned
+    /// handle is dropped
+    #[must_use = "subscription is removed on its handle drop"]
+    fn subscribe_with_handle<MsU: 'static, SubMs: 'static + Clone>(
+        &mut self,
+        handler: impl FnOnce(SubMs) -> MsU + Clone + 'static,
+    ) -> TaskHandle {
+        let alive = Rc::new(());
+        let token = Rc::downgrade(&alive);
+        self.subscribe(move |msg: SubMs| {
+            token
+                .upgrade()
+                .and_then(|_| into_msg::<Ms, MsU>(handler(msg)))
+        });
+        TaskHandle::new(move || drop(alive))
+    }
+
+    /// Send the messages produced by `stream`
+    fn stream<MsU: 'static>(&mut self, stream: impl Stream<Item = MsU> + 'static) -> &mut Self;
+
+    /// Same as `stream` but the stream is aborted once the returned handle is
+    /// dropped
+    #[must_use = "stream is aborted on its handle drop"]
+    fn stream_with_handle<MsU: 'static>(
+        &mut self,
+        stream: impl Stream<Item = MsU> + 'static,
+    ) -> TaskHandle {
+        let (stream, handle) = stream::abortable(stream);
+        self.stream(stream.map(into_msg::<Ms, MsU>));
+        TaskHandle::new(move || handle.abort())
+    }
+
+    /// Base path loaded from `<base href="/base/path/">`
+    fn clone_base_path(&self) -> Rc<Vec<String>>;
+
+    /// Simulate `<a href="[url]">` element click
+    fn request_url(&mut self, url: Url) -> &mut Self {
+        self.notify(subs::UrlRequested::new(url))
+    }
+}
+
+// Seed's orders, calls are forwarded to Seed's `Orders` methods
+macro_rules! impl_seed_orders {
+    ($ms:ident, $ty:ty, <$($lt:lifetime,)? $($param:ident),*>) => {
+        impl<$($lt,)? $($param),*> Orders<$ms> for $ty
+        where
+            $ms: 'static,
+            AppMs: 'static,
+            INodes: IntoNodes<AppMs> + 'static,
+        {
+            fn render(&mut self) -> &mut Self {
+                seed::app::Orders::render(self)
+            }
+
+            fn force_render_now(&mut self) -> &mut Self {
+                seed::app::Orders::force_render_now(self)
+            }
+
+            fn skip(&mut self) -> &mut Self {
+                seed::app::Orders::skip(self)
+            }
+
+            fn notify(&mut self, message: impl Any + Clone) -> &mut Self {
+                seed::app::Orders::notify(self, message)
+            }
+
+            fn send_msg(&mut self, msg: $ms) -> &mut Self {
+                seed::app::Orders::send_msg(self, msg)
+            }
+
+            fn perform_cmd<MsU: 'static>(
+                &mut self,
+                cmd: impl Future<Output = MsU> + 'static,
+            ) -> &mut Self {
+                seed::app::Orders::perform_cmd(self, cmd)
+            }
+
+            fn msg_sender(&self) -> Rc<dyn Fn(Option<$ms>)> {
+                seed::app::Orders::msg_sender(self)
+            }
+
+            fn after_next_render<MsU: 'static>(
+                &mut self,
+                callback: impl FnOnce(RenderInfo) -> MsU + 'static,
+            ) -> &mut Self {
+                seed::app::Orders::after_next_render(self, callback)
+            }
+
+            fn subscribe<MsU: 'static, SubMs: 'static + Clone>(
+                &mut self,
+                handler: impl FnOnce(SubMs) -> MsU + Clone + 'static,
+            ) -> &mut Self {
+                seed::app::Orders::subscribe(self, handler)
+            }
+
+            fn subscribe_with_handle<MsU: 'static, SubMs: 'static + Clone>(
+                &mut self,
+                handler: impl FnOnce(SubMs) -> MsU + Clone + 'static,
+            ) -> TaskHandle {
+                let handle = seed::app::Orders::subscribe_with_handle(self, handler);
+                TaskHandle::new(move || drop(handle))
+            }
+
+            fn stream<MsU: 'static>(
+                &mut self,
+                stream: impl Stream<Item = MsU> + 'static,
+            ) -> &mut Self {
+                seed::app::Orders::stream(self, stream)
+            }
+
+            fn clone_base_path(&self) -> Rc<Vec<String>> {
+                seed::app::Orders::clone_base_path(self)
+            }
+        }
+    };
+}
+
+impl_seed_orders!(AppMs, OrdersContainer<AppMs, Mdl, INodes>, <AppMs, Mdl, INodes>);
+impl_seed_orders!(Ms, OrdersProxy<'a, Ms, AppMs, Mdl, INodes>, <'a, Ms, AppMs, Mdl, INodes>);
 
 /// Provides convenient to interact with Seed runtime
 pub trait OrdersExt<Ms: 'static>: Orders<Ms> {
@@ -13,7 +233,7 @@ pub trait OrdersExt<Ms: 'static>: Orders<Ms> {
         ms: u32,
         handler: impl FnOnce() -> MsU + Clone + 'static,
     ) -> &mut Self {
-        self.stream(streams::interval(ms, handler))
+        self.stream(interval(ms, handler))
     }
 
     /// same as `send_every` but return handler to control it's lifetime
@@ -21,8 +241,8 @@ pub trait OrdersExt<Ms: 'static>: Orders<Ms> {
         &mut self,
         ms: u32,
         handler: impl FnOnce() -> MsU + Clone + 'static,
-    ) -> StreamHandle {
-        self.stream_with_handle(streams::interval(ms, handler))
+    ) -> TaskHandle {
+        self.stream_with_handle(interval(ms, handler))
     }
 
     /// same as `send_msg` but shorter
@@ -39,7 +259,7 @@ pub trait OrdersExt<Ms: 'static>: Orders<Ms> {
     fn cmd_with_handle<MsU: 'static>(
         &mut self,
         cmd: impl Future<Output = MsU> + 'static,
-    ) -> CmdHandle {
+    ) -> TaskHandle {
         self.perform_cmd_with_handle(cmd)
     }
 
@@ -49,7 +269,7 @@ pub trait OrdersExt<Ms: 'static>: Orders<Ms> {
         ms: u32,
         handler: impl FnOnce() -> MsU + Clone + 'static,
     ) -> &mut Self {
-        self.cmd(cmds::timeout(ms, handler))
+        self.cmd(timeout(ms, handler))
     }
 
     /// same as `send_after` but reutrns handler to control it's lifetime
@@ -57,9 +277,255 @@ pub trait OrdersExt<Ms: 'static>: Orders<Ms> {
         &mut self,
         ms: u32,
         handler: impl FnOnce() -> MsU + Clone + 'static,
-    ) -> CmdHandle {
-        self.cmd_with_handle(cmds::timeout(ms, handler))
+    ) -> TaskHandle {
+        self.cmd_with_handle(timeout(ms, handler))
+    }
+
+    /// run `handler` every time `T` changes in `env`
+    ///
+    /// # Example
+    /// ```
+    /// # use savory::prelude::*;
+    /// # #[derive(Clone)] struct Theme;
+    /// # enum Msg { Rerender }
+    /// # fn init(orders: &mut impl Orders<Msg>, env: Env) {
+    /// orders.subscribe_env(&env, |_: Theme| Msg::Rerender);
+    /// # }
+    /// ```
+    ///
+    /// See [`Env::changes`] for more details.
+    fn subscribe_env<MsU: 'static, T: Clone + 'static>(
+        &mut self,
+        env: &Env,
+        handler: impl FnOnce(T) -> MsU + Clone + 'static,
+    ) -> &mut Self {
+        self.stream(env.changes::<T>().map(move |val| handler.clone()(val)))
+    }
+
+    /// same as `subscribe_env` but return handler to control it's lifetime
+    fn subscribe_env_with_handle<MsU: 'static, T: Clone + 'static>(
+        &mut self,
+        env: &Env,
+        handler: impl FnOnce(T) -> MsU + Clone + 'static,
+    ) -> TaskHandle {
+        self.stream_with_handle(env.changes::<T>().map(move |val| handler.clone()(val)))
+    }
+
+    /// same as `subscribe_env` but for values stored using `key`
+    fn subscribe_env_key<MsU: 'static, T: Clone + 'static>(
+        &mut self,
+        env: &Env,
+        key: EnvKey<T>,
+        handler: impl FnOnce(T) -> MsU + Clone + 'static,
+    ) -> &mut Self {
+        self.stream(env.changes_key(key).map(move |val| handler.clone()(val)))
+    }
+
+    /// run `handler` every time the value returned by `selector` changes in
+    /// `store`
+    ///
+    /// See [`Store::changes`] for more details.
+    fn subscribe_store<MsU: 'static, S: Reducer, T: Clone + PartialEq + 'static>(
+        &mut self,
+        store: &Store<S>,
+        selector: impl Fn(&S) -> T + 'static,
+        handler: impl FnOnce(T) -> MsU + Clone + 'static,
+    ) -> &mut Self {
+        self.stream(store.changes(selector).map(move |val| handler.clone()(val)))
+    }
+
+    /// run `handler` every time `combo` is pressed in the window
+    ///
+    /// # Example
+    /// ```
+    /// # use savory::prelude::*;
+    /// # enum Msg { Save }
+    /// # fn init(orders: &mut impl Orders<Msg>) {
+    /// orders.subscribe_shortcut(KeyCombo::new(Key::Char('s')).ctrl(), || Msg::Save);
+    /// # }
+    /// ```
+    ///
+    /// See [`shortcuts`] for more details.
+    ///
+    /// [`shortcuts`]: crate::events::shortcuts
+    fn subscribe_shortcut<MsU: 'static>(
+        &mut self,
+        combo: impl Into<KeyCombo>,
+        handler: impl FnOnce() -> MsU + Clone + 'static,
+    ) -> &mut Self {
+        self.stream(shortcuts::listen(combo.into()).map(move |_| handler.clone()()))
+    }
+
+    /// same as `subscribe_shortcut` but return handler to control it's
+    /// lifetime
+    fn subscribe_shortcut_with_handle<MsU: 'static>(
+        &mut self,
+        combo: impl Into<KeyCombo>,
+        handler: impl FnOnce() -> MsU + Clone + 'static,
+    ) -> TaskHandle {
+        self.stream_with_handle(shortcuts::listen(combo.into()).map(move |_| handler.clone()()))
     }
 }
 
 impl<T, Ms: 'static> OrdersExt<Ms> for T where T: Orders<Ms> {}
+
+/// Handle that cancels its command, stream or subscription when dropped
+///
+/// Returned by the `*_with_handle` methods of [`Orders`].
+#[must_use = "the task is cancelled on its handle drop"]
+pub struct TaskHandle(Option<Box<dyn FnOnce()>>);
+
+impl TaskHandle {
+    /// Create handle that calls `cancel` when it's dropped
+    pub fn new(cancel: impl FnOnce() + 'static) -> Self {
+        Self(Some(Box::new(cancel)))
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        if let Some(cancel) = self.0.take() {
+            cancel();
+        }
+    }
+}
+
+impl fmt::Debug for TaskHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TaskHandle")
+    }
+}
+
+/// Orders returned by [`Orders::proxy`], messages are mapped into the messages
+/// of the orders they wrap
+pub struct MappedOrders<'a, O: ?Sized, Ms, ChildMs> {
+    orders: &'a mut O,
+    f: Rc<dyn Fn(ChildMs) -> Ms>,
+}
+
+impl<'a, O: ?Sized, Ms, ChildMs> MappedOrders<'a, O, Ms, ChildMs> {
+    pub(crate) fn new(orders: &'a mut O, f: impl FnOnce(ChildMs) -> Ms + Clone + 'static) -> Self {
+        Self {
+            orders,
+            f: Rc::new(move |msg| f.clone()(msg)),
+        }
+    }
+}
+
+impl<'a, O, Ms, ChildMs> Orders<ChildMs> for MappedOrders<'a, O, Ms, ChildMs>
+where
+    O: Orders<Ms> + ?Sized,
+    Ms: 'static,
+    ChildMs: 'static,
+{
+    fn render(&mut self) -> &mut Self {
+        self.orders.render();
+        self
+    }
+
+    fn force_render_now(&mut self) -> &mut Self {
+        self.orders.force_render_now();
+        self
+    }
+
+    fn skip(&mut self) -> &mut Self {
+        self.orders.skip();
+        self
+    }
+
+    fn notify(&mut self, message: impl Any + Clone) -> &mut Self {
+        self.orders.notify(message);
+        self
+    }
+
+    fn send_msg(&mut self, msg: ChildMs) -> &mut Self {
+        self.orders.send_msg((self.f)(msg));
+        self
+    }
+
+    fn perform_cmd<MsU: 'static>(&mut self, cmd: impl Future<Output = MsU> + 'static) -> &mut Self {
+        let f = Rc::clone(&self.f);
+        self.orders
+            .perform_cmd(cmd.map(move |msg| into_msg::<ChildMs, MsU>(msg).map(&*f)));
+        self
+    }
+
+    fn msg_sender(&self) -> Rc<dyn Fn(Option<ChildMs>)> {
+        let (sender, f) = (self.orders.msg_sender(), Rc::clone(&self.f));
+        Rc::new(move |msg| sender(msg.map(&*f)))
+    }
+
+    fn after_next_render<MsU: 'static>(
+        &mut self,
+        callback: impl FnOnce(RenderInfo) -> MsU + 'static,
+    ) -> &mut Self {
+        let f = Rc::clone(&self.f);
+        self.orders
+            .after_next_render(move |info| into_msg::<ChildMs, MsU>(callback(info)).map(&*f));
+        self
+    }
+
+    fn subscribe<MsU: 'static, SubMs: 'static + Clone>(
+        &mut self,
+        handler: impl FnOnce(SubMs) -> MsU + Clone + 'static,
+    ) -> &mut Self {
+        let f = Rc::clone(&self.f);
+        self.orders
+            .subscribe(move |msg: SubMs| into_msg::<ChildMs, MsU>(handler(msg)).map(&*f));
+        self
+    }
+
+    fn stream<MsU: 'static>(&mut self, stream: impl Stream<Item = MsU> + 'static) -> &mut Self {
+        let f = Rc::clone(&self.f);
+        self.orders
+            .stream(stream.map(move |msg| into_msg::<ChildMs, MsU>(msg).map(&*f)));
+        self
+    }
+
+    fn clone_base_path(&self) -> Rc<Vec<String>> {
+        self.orders.clone_base_path()
+    }
+}
+
+/// Convert handler output into message the same way Seed does.
+///
+/// # Panics
+/// This panics if `msg` is not `Ms`, `Option<Ms>` or `()`.
+pub(crate) fn into_msg<Ms: 'static, MsU: 'static>(msg: MsU) -> Option<Ms> {
+    let msg = &mut Some(msg) as &mut dyn Any;
+    if let Some(msg) = msg.downcast_mut::<Option<Ms>>() {
+        msg.take()
+    } else if let Some(msg) = msg.downcast_mut::<Option<Option<Ms>>>() {
+        msg.take().flatten()
+    } else if msg.is::<Option<()>>() {
+        None
+    } else {
+        panic!("Handler can return only Ms, Option<Ms> or ()!")
+    }
+}
+
+/// Same as `cmds::timeout`, unless the orders belong to a test harness, in
+/// that case the timer waits on the harness virtual clock
+fn timeout<MsU: 'static>(
+    ms: u32,
+    handler: impl FnOnce() -> MsU + Clone + 'static,
+) -> Pin<Box<dyn Future<Output = MsU>>> {
+    #[cfg(any(test, feature = "testing"))]
+    if let Some(clock) = crate::testing::clock::current() {
+        return Box::pin(clock.timeout(ms, handler));
+    }
+    Box::pin(cmds::timeout(ms, handler))
+}
+
+/// Same as `streams::interval`, unless the orders belong to a test harness,
+/// in that case the timer waits on the harness virtual clock
+fn interval<MsU: 'static>(
+    ms: u32,
+    handler: impl FnOnce() -> MsU + Clone + 'static,
+) -> Pin<Box<dyn Stream<Item = MsU>>> {
+    #[cfg(any(test, feature = "testing"))]
+    if let Some(clock) = crate::testing::clock::current() {
+        return Box::pin(clock.interval(ms, handler));
+    }
+    Box::pin(streams::interval(ms, handler))
+}
@@ -0,0 +1,335 @@
+//! Render nodes into a different DOM container.
+//!
+//! Dropdowns, tooltips and modals often need to escape their parents (e.g.
+//! parents with `overflow: hidden`), [`PortalHandle`] renders nodes into the
+//! target DOM node, while a `<savory-portal>` placeholder is rendered in place.
+//!
+//! The portal is owned by the element state, it's created in `init` and
+//! removed from the DOM once the element drops it:
+//!
+//! ```rust
+//! use savory::{portal::{PortalHandle, PortalTarget}, prelude::*};
+//!
+//! struct Modal {
+//!     portal: PortalHandle<Msg>,
+//! }
+//!
+//! enum Msg {
+//!     Close,
+//! }
+//!
+//! impl Element for Modal {
+//!     type Message = Msg;
+//!     type Config = ();
+//!
+//!     fn init(_: (), _: &mut impl Orders<Msg>, _: Env) -> Self {
+//!         Modal { portal: PortalHandle::new(PortalTarget::Body) }
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, _: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             Msg::Close => { /* .. */ }
+//!         }
+//!     }
+//! }
+//!
+//! impl View<Node<Msg>> for Modal {
+//!     fn view(&self) -> Node<Msg> {
+//!         self.portal
+//!             .view(vec![html::button().on_click(|_| Msg::Close).push("Close")])
+//!     }
+//! }
+//! ```
+//!
+//! The content is rendered by the owner app inside a `<div
+//! data-savory-portal>` wrapper in the placeholder, and the wrapper is moved to
+//! the target right after Seed renders it. Seed keeps patching the wrapper in
+//! its new place, so event handlers inside the portal send their messages to
+//! the owner app as if the nodes were rendered in place. Note that DOM events
+//! bubble from the wrapper to the target, not to the placeholder parents.
+//!
+//! The rendered content stays in the target until the next render of the
+//! placeholder or until the handle is dropped, so render the placeholder with
+//! no children to hide the portal content.
+//!
+//! Outside the browser (e.g. tests and server side rendering) the nodes are
+//! rendered inside the placeholder.
+
+use seed::prelude::{El, Node, Tag};
+use std::borrow::Cow;
+
+/// Tag of the placeholder rendered in place of the portal
+const TAG: &str = "savory-portal";
+
+/// DOM node that portal content get rendered into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortalTarget {
+    /// Document's `body`
+    Body,
+    /// Element with the given id
+    Id(Cow<'static, str>),
+}
+
+impl PortalTarget {
+    pub fn id(id: impl Into<Cow<'static, str>>) -> Self {
+        PortalTarget::Id(id.into())
+    }
+}
+
+/// Portal owned by an element
+///
+/// See [module docs](crate::portal) for more details.
+pub struct PortalHandle<Msg: 'static> {
+    target: PortalTarget,
+    #[cfg(target_arch = "wasm32")]
+    state: std::rc::Rc<dom::State>,
+    _msg: std::marker::PhantomData<Msg>,
+}
+
+impl<Msg: 'static> PortalHandle<Msg> {
+    /// Create portal that renders into `target`, the portal content is
+    /// removed from the DOM once the handle is dropped
+    pub fn new(target: PortalTarget) -> Self {
+        Self {
+            #[cfg(target_arch = "wasm32")]
+            state: dom::State::new(target.clone()),
+            _msg: std::marker::PhantomData,
+            target,
+        }
+    }
+
+    pub fn target(&self) -> &PortalTarget {
+        &self.target
+    }
+
+    /// Return the placeholder node that should be rendered in place, the
+    /// placeholder renders `children` into the portal target
+    ///
+    /// # Panics
+    ///
+    /// This panics if the target doesn't exist when the placeholder is
+    /// rendered.
+    pub fn view(&self, children: Vec<Node<Msg>>) -> Node<Msg> {
+        let mut el = El::empty(Tag::from(TAG));
+
+        #[cfg(target_arch = "wasm32")]
+        el.children.push(self.state.wrapper(children));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            el.children = children;
+        }
+
+        Node::Element(el)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod dom {
+    use super::PortalTarget;
+    use crate::prelude::*;
+    use seed::prelude::{El, Node, Tag};
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    pub(super) struct State {
+        target: PortalTarget,
+        // wrapper rendered by the last render of the placeholder
+        wrapper: ElRef<web_sys::Element>,
+        // wrapper that was moved to the target
+        moved: RefCell<Option<web_sys::Element>>,
+        scheduled: Cell<bool>,
+    }
+
+    impl State {
+        pub fn new(target: PortalTarget) -> Rc<Self> {
+            Rc::new(State {
+                target,
+                wrapper: ElRef::default(),
+                moved: RefCell::new(None),
+                scheduled: Cell::new(false),
+            })
+        }
+
+        pub fn wrapper<Msg>(self: &Rc<Self>, children: Vec<Node<Msg>>) -> Node<Msg> {
+            // views are called right before Seed patches the DOM, the spawned
+            // task runs once the patch is done, so the content is moved before
+            // the browser paints it in place
+            if !self.scheduled.replace(true) {
+                let state = Rc::downgrade(self);
+                seed::spawn_local(async move {
+                    if let Some(state) = state.upgrade() {
+                        state.scheduled.set(false);
+                        state.move_wrapper();
+                    }
+                });
+            }
+
+            let mut wrapper = El::empty(Tag::Div).set_attr("data-savory-portal", "");
+            wrapper.refs.push(self.wrapper.clone().shared_node_ws);
+            wrapper.children = children;
+            Node::Element(wrapper)
+        }
+
+        /// Move the rendered wrapper to the target if it's not moved yet
+        fn move_wrapper(&self) {
+            let wrapper = match self.wrapper.get() {
+                Some(wrapper) => wrapper,
+                // the placeholder wasn't rendered
+                None => return,
+            };
+            let mut moved = self.moved.borrow_mut();
+            if moved.as_ref() == Some(&wrapper) {
+                return;
+            }
+
+            let document = seed::browser::util::document();
+            let target: web_sys::Element = match &self.target {
+                PortalTarget::Body => document.body().expect("document has body").into(),
+                PortalTarget::Id(id) => document
+                    .get_element_by_id(id)
+                    .unwrap_or_else(|| panic!("portal target `#{}` isn't found", id)),
+            };
+            target
+                .append_child(&wrapper)
+                .expect("append portal wrapper");
+            // Seed rendered a new placeholder, the old one is no longer
+            // rendered
+            if let Some(old) = moved.replace(wrapper) {
+                old.remove();
+            }
+        }
+    }
+
+    impl Drop for State {
+        fn drop(&mut self) {
+            if let Some(wrapper) = self.moved.get_mut().take() {
+                wrapper.remove();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_portal_in_place() {
+        let portal = PortalHandle::<()>::new(PortalTarget::id("overlays"));
+        let node = portal.view(vec![html::span().push("Hint")]);
+        assert_eq!(
+            node.to_html(),
+            "<savory-portal><span>Hint</span></savory-portal>"
+        );
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    mod browser {
+        use super::*;
+        use seed::{
+            app::App,
+            browser::util::{document, window},
+            prelude::{js_sys, JsCast},
+            JsFuture,
+        };
+        use std::{cell::Cell, rc::Rc};
+        use wasm_bindgen_test::*;
+
+        wasm_bindgen_test_configure!(run_in_browser);
+
+        async fn next_frame() {
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                window()
+                    .request_animation_frame(&resolve)
+                    .expect("request animation frame");
+            });
+            JsFuture::from(promise).await.expect("wait next frame");
+        }
+
+        fn target(id: &str) -> web_sys::Element {
+            let target = document().create_element("div").unwrap();
+            target.set_id(id);
+            document().body().unwrap().append_child(&target).unwrap();
+            target
+        }
+
+        fn portal_button(target: &web_sys::Element) -> Option<web_sys::HtmlElement> {
+            target
+                .query_selector("[data-savory-portal] button")
+                .expect("query portal button")
+                .map(|button| button.unchecked_into())
+        }
+
+        struct Owner {
+            portal: PortalHandle<Msg>,
+            clicks: Rc<Cell<u32>>,
+        }
+
+        enum Msg {
+            Clicked,
+        }
+
+        #[wasm_bindgen_test]
+        async fn test_click_inside_portal_reaches_owner() {
+            let root = target("owner");
+            let overlays = target("owner-overlays");
+
+            let clicks = Rc::new(Cell::new(0));
+            let _app = App::start(
+                root.clone(),
+                {
+                    let clicks = Rc::clone(&clicks);
+                    move |_, _| Owner {
+                        portal: PortalHandle::new(PortalTarget::id("owner-overlays")),
+                        clicks,
+                    }
+                },
+                |msg, owner: &mut Owner, _| match msg {
+                    Msg::Clicked => owner.clicks.set(owner.clicks.get() + 1),
+                },
+                |owner: &Owner| -> Node<Msg> {
+                    owner.portal.view(vec![html::button()
+                        .on_click(|_| Msg::Clicked)
+                        .push("Close")])
+                },
+            );
+            next_frame().await;
+            next_frame().await;
+
+            let button = portal_button(&overlays).expect("portal content is rendered");
+            assert!(!root.contains(Some(&button)));
+            assert!(root.query_selector("savory-portal").unwrap().is_some());
+
+            button.click();
+            assert_eq!(clicks.get(), 1);
+        }
+
+        #[wasm_bindgen_test]
+        async fn test_drop_removes_portal() {
+            let root = target("dropped");
+            let overlays = target("dropped-overlays");
+
+            let app = App::start(
+                root,
+                |_, _| Some(PortalHandle::new(PortalTarget::id("dropped-overlays"))),
+                |_: (), portal: &mut Option<PortalHandle<()>>, _| *portal = None,
+                |portal: &Option<PortalHandle<()>>| -> Node<()> {
+                    match portal {
+                        Some(portal) => portal.view(vec![html::button().push("Close")]),
+                        None => html::div(),
+                    }
+                },
+            );
+            next_frame().await;
+            assert!(portal_button(&overlays).is_some());
+
+            app.update(());
+            next_frame().await;
+            assert!(portal_button(&overlays).is_none());
+        }
+    }
+}
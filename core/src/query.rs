@@ -0,0 +1,591 @@
+//! Query nodes using CSS like selectors.
+//!
+//! This is mostly useful in tests, where we want to find some element in the
+//! view and assert on it's attributes, style, text or events.
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! enum Msg {
+//!     Save,
+//! }
+//!
+//! let node: Node<Msg> = html::form()
+//!     .push(html::input().class("name").disabled(true))
+//!     .push(
+//!         html::button()
+//!             .class("button")
+//!             .id("save")
+//!             .push("Save")
+//!             .on_click(|_| Msg::Save),
+//!     );
+//!
+//! let button = node.query(".button").unwrap();
+//! assert_eq!(button.text(), "Save");
+//! assert_eq!(button.id(), Some("save"));
+//! assert!(button.handles(Ev::Click));
+//!
+//! assert!(node.query("form > input.name[disabled]").unwrap().is_disabled());
+//! assert!(node.query("#cancel").is_none());
+//! ```
+//!
+//! Supported selectors:
+//! - `*` and tag selectors (e.g. `button`)
+//! - class selectors (e.g. `.button`)
+//! - id selectors (e.g. `#save`)
+//! - attribute selectors (e.g. `[disabled]`, `[type=text]` or `[type="text"]`)
+//! - descendant (e.g. `form input`) and child (e.g. `form > input`)
+//!   combinators
+
+use crate::{events, prelude::*};
+use seed::prelude::{At, AtValue, CSSValue, Ev, IndexMap, St};
+use std::{fmt, str::FromStr};
+#[cfg(target_arch = "wasm32")]
+use {
+    seed::virtual_dom::Mailbox,
+    std::{cell::RefCell, rc::Rc},
+};
+
+/// Parsed selector
+///
+/// Selectors can be parsed using `str::parse`:
+/// ```rust
+/// # use savory::query::Selector;
+/// let selector: Selector = "ul > li.active".parse().unwrap();
+/// assert!("ul >".parse::<Selector>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    // compounds ordered from the leftmost to the rightmost, each compound has
+    // the combinator that relates it to the previous compound
+    compounds: Vec<(Combinator, Compound)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, Option<String>)>,
+}
+
+/// Error returned when parsing invalid selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorError {
+    pub selector: String,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid selector `{}`: {}", self.selector, self.reason)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let err = |reason| SelectorError {
+            selector: selector.to_string(),
+            reason,
+        };
+
+        let mut compounds = vec![];
+        let mut combinator = Combinator::Descendant;
+        let mut chars = selector.trim().chars().peekable();
+        let mut compound: Option<Compound> = None;
+
+        let ident = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+            let mut ident = String::new();
+            while let Some(c) = chars.peek().copied().filter(|c| is_ident_char(*c)) {
+                ident.push(c);
+                chars.next();
+            }
+            ident
+        };
+
+        while let Some(c) = chars.peek().copied() {
+            match c {
+                ' ' | '>' => {
+                    chars.next();
+                    if let Some(compound) = compound.take() {
+                        compounds.push((combinator, compound));
+                        combinator = Combinator::Descendant;
+                    }
+                    if c == '>' {
+                        if compounds.is_empty() || combinator == Combinator::Child {
+                            return Err(err("misplaced `>`"));
+                        }
+                        combinator = Combinator::Child;
+                    }
+                }
+                '*' => {
+                    chars.next();
+                    compound.get_or_insert_with(Compound::default);
+                }
+                '.' | '#' => {
+                    chars.next();
+                    let name = ident(&mut chars);
+                    if name.is_empty() {
+                        return Err(err("expected name after `.` or `#`"));
+                    }
+                    let compound = compound.get_or_insert_with(Compound::default);
+                    if c == '.' {
+                        compound.classes.push(name);
+                    } else {
+                        compound.id = Some(name);
+                    }
+                }
+                '[' => {
+                    chars.next();
+                    let name = ident(&mut chars);
+                    if name.is_empty() {
+                        return Err(err("expected attribute name after `[`"));
+                    }
+                    let value = match chars.next() {
+                        Some(']') => None,
+                        Some('=') => {
+                            let mut value = String::new();
+                            loop {
+                                match chars.next() {
+                                    Some(']') => break,
+                                    Some(c) => value.push(c),
+                                    None => return Err(err("expected `]`")),
+                                }
+                            }
+                            Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+                        }
+                        _ => return Err(err("expected `]` or `=`")),
+                    };
+                    compound
+                        .get_or_insert_with(Compound::default)
+                        .attrs
+                        .push((name, value));
+                }
+                c if is_ident_char(c) => {
+                    let compound = compound.get_or_insert_with(Compound::default);
+                    if compound != &Compound::default() {
+                        return Err(err("tag must come first in compound selector"));
+                    }
+                    compound.tag = Some(ident(&mut chars));
+                }
+                _ => return Err(err("unsupported character")),
+            }
+        }
+
+        match compound {
+            Some(compound) => compounds.push((combinator, compound)),
+            None if compounds.is_empty() => return Err(err("empty selector")),
+            None => return Err(err("selector ends with combinator")),
+        }
+
+        Ok(Selector { compounds })
+    }
+}
+
+impl Compound {
+    fn matches<Msg>(&self, el: &El<Msg>) -> bool {
+        let el = Match { el };
+        self.tag
+            .as_ref()
+            .is_none_or(|tag| el.tag().eq_ignore_ascii_case(tag))
+            && self.id.as_ref().is_none_or(|id| el.id() == Some(id))
+            && self.classes.iter().all(|class| el.has_class(class))
+            && self.attrs.iter().all(|(name, value)| match value {
+                Some(value) => el.attr(name) == Some(value.as_str()),
+                None => el.has_attr(name),
+            })
+    }
+}
+
+impl Selector {
+    /// Check if `el` matches this selector, `ancestors` are ordered from the
+    /// root to the parent of `el`
+    fn matches<Msg>(&self, el: &El<Msg>, ancestors: &[&El<Msg>]) -> bool {
+        fn matches_at<Msg>(
+            compounds: &[(Combinator, Compound)],
+            el: &El<Msg>,
+            ancestors: &[&El<Msg>],
+        ) -> bool {
+            let ((combinator, compound), rest) = match compounds.split_last() {
+                Some(last) => last,
+                None => return true,
+            };
+            if !compound.matches(el) {
+                return false;
+            }
+            if rest.is_empty() {
+                return true;
+            }
+            match combinator {
+                Combinator::Child => match ancestors.split_last() {
+                    Some((parent, ancestors)) => matches_at(rest, parent, ancestors),
+                    None => false,
+                },
+                Combinator::Descendant => (0..ancestors.len())
+                    .rev()
+                    .any(|index| matches_at(rest, ancestors[index], &ancestors[..index])),
+            }
+        }
+
+        matches_at(&self.compounds, el, ancestors)
+    }
+}
+
+/// Element that matched a selector
+pub struct Match<'a, Msg> {
+    el: &'a El<Msg>,
+}
+
+impl<'a, Msg> Clone for Match<'a, Msg> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Msg> Copy for Match<'a, Msg> {}
+
+impl<'a, Msg> fmt::Debug for Match<'a, Msg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Match")
+            .field("tag", &self.tag())
+            .field("attrs", &self.attrs())
+            .finish()
+    }
+}
+
+impl<'a, Msg> Match<'a, Msg> {
+    /// The matched element
+    pub fn el(&self) -> &'a El<Msg> {
+        self.el
+    }
+
+    pub fn tag(&self) -> &'a str {
+        self.el.tag.as_str()
+    }
+
+    /// Attribute value, attributes that have no value (e.g. `hidden`) return
+    /// empty string
+    pub fn attr(&self, name: &str) -> Option<&'a str> {
+        match self.el.attrs.vals.get(&At::from(name.to_string())) {
+            Some(AtValue::Some(value)) => Some(value),
+            Some(AtValue::None) => Some(""),
+            Some(AtValue::Ignored) | None => None,
+        }
+    }
+
+    pub fn has_attr(&self, name: &str) -> bool {
+        self.attr(name).is_some()
+    }
+
+    /// All attributes in the order they were added
+    pub fn attrs(&self) -> Vec<(&'a str, &'a str)> {
+        self.el
+            .attrs
+            .vals
+            .iter()
+            .filter_map(|(name, value)| match value {
+                AtValue::Some(value) => Some((name.as_str(), value.as_str())),
+                AtValue::None => Some((name.as_str(), "")),
+                AtValue::Ignored => None,
+            })
+            .collect()
+    }
+
+    pub fn id(&self) -> Option<&'a str> {
+        self.attr("id")
+    }
+
+    pub fn classes(&self) -> Vec<&'a str> {
+        self.attr("class")
+            .map(|class| class.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes().contains(&class)
+    }
+
+    /// Check if the element have `disabled` attribute, regardless of its
+    /// value (`disabled="false"` still disables the element)
+    pub fn is_disabled(&self) -> bool {
+        self.attr("disabled").is_some()
+    }
+
+    /// Inline style computed from the `style` attribute and the element style,
+    /// the element style wins when both have the same property
+    pub fn style(&self) -> IndexMap<St, String> {
+        let mut style = IndexMap::new();
+        if let Some(attr) = self.attr("style") {
+            for prop in attr.split(';') {
+                if let Some((name, value)) = prop.split_once(':') {
+                    style.insert(St::from(name.trim().to_string()), value.trim().to_string());
+                }
+            }
+        }
+        for (name, value) in self.el.style.vals.iter() {
+            if let CSSValue::Some(value) = value {
+                style.insert(name.clone(), value.clone());
+            }
+        }
+        style
+    }
+
+    /// Text content of the element and its descendants
+    pub fn text(&self) -> String {
+        fn collect<Msg>(nodes: &[Node<Msg>], text: &mut String) {
+            for node in nodes {
+                match node {
+                    Node::Element(el) => collect(&el.children, text),
+                    Node::Text(txt) => text.push_str(&txt.text),
+                    Node::Empty | Node::NoChange => {}
+                }
+            }
+        }
+        let mut text = String::new();
+        collect(&self.el.children, &mut text);
+        text
+    }
+
+    /// Query this element descendants
+    pub fn query(&self, selector: &str) -> Option<Match<'a, Msg>> {
+        self.query_all(selector).into_iter().next()
+    }
+
+    /// Query all this element descendants
+    pub fn query_all(&self, selector: &str) -> Vec<Match<'a, Msg>> {
+        let selector = parse(selector);
+        let mut matches = vec![];
+        walk(
+            &self.el.children,
+            &selector,
+            &mut vec![self.el],
+            &mut matches,
+        );
+        matches
+    }
+}
+
+impl<'a, Msg: 'static> Match<'a, Msg> {
+    /// Events that the element have handlers for
+    ///
+    /// Only handlers added using `EventsApi` (e.g. `on_click`) are reported,
+    /// since Seed keeps the handlers of elements private.
+    pub fn triggers(&self) -> Vec<Ev> {
+        events::triggers(self.el)
+    }
+
+    /// Check if the element have handler for `trigger` event
+    pub fn handles(&self, trigger: impl Into<Ev>) -> bool {
+        self.triggers().contains(&trigger.into())
+    }
+
+    /// Simulate `trigger` event on the element and return the messages
+    /// produced by its handlers
+    ///
+    /// The handlers receive plain `web_sys::Event`, use `simulate_with` for
+    /// handlers that need specific event type (e.g. `MouseEvent`).
+    ///
+    /// Only available on `wasm32` targets: Seed keeps event handlers private,
+    /// they can only be called through DOM listeners, and events can't be
+    /// created outside the browser, so tests that simulate events run using
+    /// `wasm-bindgen-test`.
+    #[cfg(target_arch = "wasm32")]
+    pub fn simulate(&self, trigger: impl Into<Ev>) -> Vec<Msg> {
+        let event = web_sys::Event::new(trigger.into().as_str()).expect("create event");
+        self.simulate_with(event)
+    }
+
+    /// Same as `simulate` but dispatches the passed `event`
+    ///
+    /// Only available on `wasm32` targets, see `simulate`.
+    #[cfg(target_arch = "wasm32")]
+    pub fn simulate_with(&self, event: impl AsRef<web_sys::Event>) -> Vec<Msg> {
+        let messages = Rc::new(RefCell::new(vec![]));
+        let mailbox = Mailbox::new({
+            let messages = Rc::clone(&messages);
+            move |msg| messages.borrow_mut().extend(msg)
+        });
+        let target = seed::browser::util::document()
+            .create_element(self.tag())
+            .expect("create element");
+        // listeners are removed once the manager is dropped
+        let mut manager = self.el.event_handler_manager.clone();
+        manager.attach_listeners(target.clone(), None, &mailbox);
+        target
+            .dispatch_event(event.as_ref())
+            .expect("dispatch event");
+        drop(manager);
+        messages.replace(vec![])
+    }
+}
+
+fn parse(selector: &str) -> Selector {
+    selector.parse().unwrap_or_else(|err| panic!("{}", err))
+}
+
+fn walk<'a, Msg>(
+    nodes: &'a [Node<Msg>],
+    selector: &Selector,
+    ancestors: &mut Vec<&'a El<Msg>>,
+    matches: &mut Vec<Match<'a, Msg>>,
+) {
+    for node in nodes {
+        if let Node::Element(el) = node {
+            if selector.matches(el, ancestors) {
+                matches.push(Match { el });
+            }
+            ancestors.push(el);
+            walk(&el.children, selector, ancestors, matches);
+            ancestors.pop();
+        }
+    }
+}
+
+/// Query API, implemented for nodes and elements
+///
+/// # Panics
+/// Query methods panics if the selector is invalid, use `Selector::from_str`
+/// to check selectors.
+pub trait QueryApi<Msg> {
+    /// First element (in document order) that matches `selector`
+    fn query(&self, selector: &str) -> Option<Match<'_, Msg>>;
+
+    /// All elements that matches `selector` in document order
+    fn query_all(&self, selector: &str) -> Vec<Match<'_, Msg>>;
+}
+
+impl<Msg> QueryApi<Msg> for [Node<Msg>] {
+    fn query(&self, selector: &str) -> Option<Match<'_, Msg>> {
+        self.query_all(selector).into_iter().next()
+    }
+
+    fn query_all(&self, selector: &str) -> Vec<Match<'_, Msg>> {
+        let selector = parse(selector);
+        let mut matches = vec![];
+        walk(self, &selector, &mut vec![], &mut matches);
+        matches
+    }
+}
+
+impl<Msg> QueryApi<Msg> for Vec<Node<Msg>> {
+    fn query(&self, selector: &str) -> Option<Match<'_, Msg>> {
+        self.as_slice().query(selector)
+    }
+
+    fn query_all(&self, selector: &str) -> Vec<Match<'_, Msg>> {
+        self.as_slice().query_all(selector)
+    }
+}
+
+impl<Msg> QueryApi<Msg> for Node<Msg> {
+    fn query(&self, selector: &str) -> Option<Match<'_, Msg>> {
+        std::slice::from_ref(self).query(selector)
+    }
+
+    fn query_all(&self, selector: &str) -> Vec<Match<'_, Msg>> {
+        std::slice::from_ref(self).query_all(selector)
+    }
+}
+
+impl<Msg> QueryApi<Msg> for El<Msg> {
+    fn query(&self, selector: &str) -> Option<Match<'_, Msg>> {
+        self.query_all(selector).into_iter().next()
+    }
+
+    fn query_all(&self, selector: &str) -> Vec<Match<'_, Msg>> {
+        let selector = parse(selector);
+        let mut matches = vec![];
+        if selector.matches(self, &[]) {
+            matches.push(Match { el: self });
+        }
+        walk(&self.children, &selector, &mut vec![self], &mut matches);
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let node: Node<()> = html::ul()
+            .class("menu")
+            .push(html::li().class("item active").push("Home"))
+            .push(
+                html::li()
+                    .class("item")
+                    .push(html::ul().push(html::li().set_attr("data-depth", "2").push("Nested"))),
+            );
+
+        let texts = |selector| {
+            node.query_all(selector)
+                .iter()
+                .map(|m| m.text())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(texts(".menu > li.item"), vec!["Home", "Nested"]);
+        assert_eq!(texts("li.active"), vec!["Home"]);
+        assert_eq!(texts(".menu li li"), vec!["Nested"]);
+        assert_eq!(texts("ul.menu > li > li"), Vec::<String>::new());
+        assert_eq!(texts("[data-depth=\"2\"]"), vec!["Nested"]);
+        assert_eq!(texts("* > ul"), vec!["Nested"]);
+        assert!("li >> a".parse::<Selector>().is_err());
+        assert!(".".parse::<Selector>().is_err());
+    }
+
+    #[test]
+    fn test_triggers() {
+        let node: Node<u8> = html::div()
+            .on_click(|_| ())
+            .on_wheel_with(|_| (), Modifiers::passive())
+            .on_submit_with(|_| (), Modifiers::prevent_default())
+            .on_click_with(|_| (), Modifiers::capture() | Modifiers::once());
+        let div = node.query("div").unwrap();
+        assert_eq!(div.triggers(), vec![Ev::Click, Ev::Wheel, Ev::Submit]);
+        // reading the triggers doesn't touch the handlers
+        assert_eq!(div.triggers(), vec![Ev::Click, Ev::Wheel, Ev::Submit]);
+        // modifiers leave nothing in the markup
+        assert!(div.attrs().is_empty());
+        assert_eq!(node.to_html(), "<div></div>");
+    }
+
+    #[test]
+    fn test_disabled() {
+        let node: Node<()> = html::div()
+            .push(html::input().class("on").set_attr("disabled", "false"))
+            .push(html::input().class("off"));
+        assert!(node.query(".on").unwrap().is_disabled());
+        assert!(!node.query(".off").unwrap().is_disabled());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    mod browser {
+        use super::*;
+        use wasm_bindgen_test::*;
+
+        wasm_bindgen_test_configure!(run_in_browser);
+
+        #[wasm_bindgen_test]
+        fn test_simulate() {
+            let node: Node<u32> = html::div().push(html::button().on_click(|_| 1).on_click(|_| 2));
+            let button = node.query("button").unwrap();
+            assert_eq!(button.simulate(Ev::Click), vec![1, 2]);
+            assert_eq!(button.simulate(Ev::Input), Vec::<u32>::new());
+        }
+    }
+}
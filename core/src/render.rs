@@ -0,0 +1,220 @@
+//! Render nodes into HTML strings.
+//!
+//! Views normally get mounted by Seed in the browser, but sometimes we need
+//! their markup as plain HTML, e.g. to prerender landing pages, send emails or
+//! assert on them in unit tests. [`ToHtml`] walks `Node` trees and produce
+//! escaped HTML, it doesn't touch the DOM so it works natively outside wasm.
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! use savory_style::{prelude::*, values as val, Style};
+//!
+//! let style = Style::default().display(val::Flex);
+//! let node: Node<()> = html::div()
+//!     .class("greeting")
+//!     .style(style.clone())
+//!     .push("Hello <World>");
+//!
+//! assert_eq!(
+//!     node.to_html(),
+//!     format!(
+//!         r#"<div class="greeting" style="{}">Hello &lt;World&gt;</div>"#,
+//!         style.to_css().unwrap()
+//!     )
+//! );
+//! ```
+//!
+//! Event handlers and element references are not part of the markup, thus they
+//! are ignored while rendering.
+
+use crate::prelude::*;
+use seed::{
+    browser::dom::Namespace,
+    prelude::{At, AtValue, CSSValue},
+};
+
+/// Elements that cannot have any child nodes, they are rendered without
+/// closing tag.
+///
+/// See <https://developer.mozilla.org/en-US/docs/Glossary/Empty_element>
+pub(crate) const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements that their text content is rendered as it is without escaping.
+pub(crate) const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Trait used to render `Self` into HTML string.
+pub trait ToHtml {
+    /// Write the rendered HTML into `out`.
+    fn write_html(&self, out: &mut String);
+
+    /// Render `Self` into HTML string.
+    fn to_html(&self) -> String {
+        let mut out = String::new();
+        self.write_html(&mut out);
+        out
+    }
+}
+
+impl<Msg> ToHtml for Node<Msg> {
+    fn write_html(&self, out: &mut String) {
+        write_node(self, None, false, out)
+    }
+}
+
+impl<Msg> ToHtml for El<Msg> {
+    fn write_html(&self, out: &mut String) {
+        write_el(self, None, out)
+    }
+}
+
+impl<Msg> ToHtml for [Node<Msg>] {
+    fn write_html(&self, out: &mut String) {
+        self.iter().for_each(|node| node.write_html(out))
+    }
+}
+
+impl<Msg> ToHtml for Vec<Node<Msg>> {
+    fn write_html(&self, out: &mut String) {
+        self.as_slice().write_html(out)
+    }
+}
+
+/// Render view into HTML string.
+///
+/// This is a shortcut for `view.view().to_html()`.
+pub fn view_to_html<Msg>(view: &impl View<Node<Msg>>) -> String {
+    view.view().to_html()
+}
+
+fn write_node<Msg>(node: &Node<Msg>, namespace: Option<&Namespace>, raw: bool, out: &mut String) {
+    match node {
+        Node::Element(el) => write_el(el, namespace, out),
+        Node::Text(text) if raw => out.push_str(&text.text),
+        Node::Text(text) => escape_into(&text.text, false, out),
+        Node::Empty | Node::NoChange => {}
+    }
+}
+
+fn write_el<Msg>(el: &El<Msg>, parent_ns: Option<&Namespace>, out: &mut String) {
+    let tag = el.tag.as_str();
+    out.push('<');
+    out.push_str(tag);
+
+    // elements that changes the namespace of their parent need to declare it,
+    // e.g. `svg` elements inside `div`
+    let namespace = el.namespace.as_ref().filter(|ns| **ns != Namespace::Html);
+    if namespace.is_some() && namespace != parent_ns {
+        write_attr("xmlns", namespace.map(|ns| ns.as_str()), out);
+    }
+
+    for (name, value) in el.attrs.vals.iter() {
+        match (name, value) {
+            (At::Style, _) => {}
+            (_, AtValue::Some(val)) => write_attr(name.as_str(), Some(val), out),
+            (_, AtValue::None) => write_attr(name.as_str(), None, out),
+            (_, AtValue::Ignored) => {}
+        }
+    }
+    if let Some(ref css) = style_attr(el) {
+        write_attr("style", Some(css), out);
+    }
+
+    let tag_lowercase = tag.to_lowercase();
+    if namespace.is_none() && VOID_ELEMENTS.contains(&tag_lowercase.as_str()) {
+        out.push('>');
+    } else if namespace.is_some() && el.children.is_empty() {
+        out.push_str("/>");
+    } else {
+        out.push('>');
+        let raw = RAW_TEXT_ELEMENTS.contains(&tag_lowercase.as_str());
+        for child in el.children.iter() {
+            write_node(child, namespace, raw, out);
+        }
+        out.push_str("</");
+        out.push_str(tag);
+        out.push('>');
+    }
+}
+
+fn write_attr(name: &str, value: Option<&str>, out: &mut String) {
+    out.push(' ');
+    out.push_str(name);
+    if let Some(value) = value {
+        out.push_str("=\"");
+        escape_into(value, true, out);
+        out.push('"');
+    }
+}
+
+/// Value of the `style` attribute, this merge the `style` attribute (if any)
+/// with the element style.
+pub(crate) fn style_attr<Msg>(el: &El<Msg>) -> Option<String> {
+    let style = match el.attrs.vals.get(&At::Style) {
+        Some(AtValue::Some(val)) => Some(val.clone()),
+        _ => None,
+    };
+    match style_to_css(&el.style) {
+        Some(css) => Some(style.unwrap_or_default() + &css),
+        None => style,
+    }
+}
+
+/// Same format used by `savory_style::Style::to_css`
+fn style_to_css(style: &seed::Style) -> Option<String> {
+    style
+        .vals
+        .iter()
+        .filter_map(|(key, value)| match value {
+            CSSValue::Some(value) => Some(format!("{}: {};", key.as_str(), value)),
+            CSSValue::Ignored => None,
+        })
+        .fold(None, |css, prop| Some(css.unwrap_or_default() + &prop))
+}
+
+pub(crate) fn escape_into(text: &str, attr: bool, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if attr => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seed::prelude::St;
+
+    #[test]
+    fn test_render() {
+        let node: Node<()> = html::div()
+            .id("main")
+            .class("card")
+            .push(html::h1().push("Tom & \"Jerry\""))
+            .push(html::input().value("<\"quoted\">").disabled(true))
+            .push(html::br())
+            .push(html::svg().push(html::circle().r(4.0)))
+            .and_el(|mut el| {
+                el.add_style(St::Display, "flex");
+                el
+            });
+
+        assert_eq!(
+            node.to_html(),
+            concat!(
+                r#"<div id="main" class="card" style="display: flex;">"#,
+                r#"<h1>Tom &amp; "Jerry"</h1>"#,
+                r#"<input value="&lt;&quot;quoted&quot;&gt;" disabled="">"#,
+                r#"<br>"#,
+                r#"<svg xmlns="http://www.w3.org/2000/svg"><circle r="4"/></svg>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+}
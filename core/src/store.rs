@@ -0,0 +1,244 @@
+//! Global state store shared through `Env`.
+//!
+//! [`Store`] holds app-wide state (e.g. current user or feature flags), the
+//! state is changed by dispatching actions that are handled by the state
+//! [`Reducer`] implementation. Stores are shared by cloning them, so inserting
+//! a store into the root `Env` makes it accessible from every branch, and
+//! unlike plain values in `Env` all clones see the latest state.
+//!
+//! Elements subscribe to parts of the state using selectors, and get a message
+//! every time the selected value changes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use savory::prelude::*;
+//!
+//! #[derive(Default)]
+//! struct AppState {
+//!     user: Option<String>,
+//!     cart: Vec<u32>,
+//! }
+//!
+//! enum Action {
+//!     LogIn(String),
+//!     AddToCart(u32),
+//! }
+//!
+//! impl Reducer for AppState {
+//!     type Action = Action;
+//!
+//!     fn reduce(&mut self, action: Action) {
+//!         match action {
+//!             Action::LogIn(user) => self.user = Some(user),
+//!             Action::AddToCart(item) => self.cart.push(item),
+//!         }
+//!     }
+//! }
+//!
+//! struct CartCounter {
+//!     count: usize,
+//! }
+//!
+//! enum Msg {
+//!     CartChanged(usize),
+//! }
+//!
+//! impl Element for CartCounter {
+//!     type Message = Msg;
+//!     type Config = ();
+//!
+//!     fn init(_: (), orders: &mut impl Orders<Msg>, env: Env) -> Self {
+//!         let store = env.store::<AppState>();
+//!         orders.subscribe_store(&store, |state| state.cart.len(), Msg::CartChanged);
+//!         CartCounter {
+//!             count: store.select(|state| state.cart.len()),
+//!         }
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, _: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             Msg::CartChanged(count) => self.count = count,
+//!         }
+//!     }
+//! }
+//!
+//! let env = Env::base_branch().insert(Store::new(AppState::default()));
+//! let mut counter = savory::testing::Harness::<CartCounter>::with_env((), env.branch());
+//!
+//! env.store::<AppState>().dispatch(Action::AddToCart(7));
+//! counter.settle();
+//! assert_eq!(counter.element().count, 1);
+//! ```
+
+use crate::env::Env;
+use seed::futures::{channel::mpsc, Stream};
+use std::{
+    any::type_name,
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+/// State that can be changed by actions
+pub trait Reducer: 'static {
+    /// Actions used to change the state
+    type Action;
+
+    /// Apply `action` on the state
+    fn reduce(&mut self, action: Self::Action);
+}
+
+/// Notify selector subscriber, returns `false` if nobody is listening anymore
+type Subscriber<S> = Box<dyn FnMut(&S) -> bool>;
+
+struct Inner<S> {
+    state: S,
+    subscribers: Vec<Subscriber<S>>,
+}
+
+/// Shared state store
+///
+/// Cloning the store is cheap and the clones share the same state.
+///
+/// See [module docs](crate::store) for more details.
+pub struct Store<S> {
+    inner: Rc<RefCell<Inner<S>>>,
+}
+
+impl<S> Clone for Store<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<S: Reducer> Store<S> {
+    pub fn new(state: S) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                state,
+                subscribers: vec![],
+            })),
+        }
+    }
+
+    /// Apply `action` on the state and notify subscribers which selected
+    /// values changed
+    ///
+    /// # Panics
+    ///
+    /// This panics if the state is borrowed using `state`.
+    pub fn dispatch(&self, action: S::Action) {
+        let mut inner = self.inner.borrow_mut();
+        let Inner { state, subscribers } = &mut *inner;
+        state.reduce(action);
+        subscribers.retain_mut(|notify| notify(state));
+    }
+
+    /// Borrow the current state
+    pub fn state(&self) -> Ref<'_, S> {
+        Ref::map(self.inner.borrow(), |inner| &inner.state)
+    }
+
+    /// Select a value from the current state
+    pub fn select<T>(&self, selector: impl FnOnce(&S) -> T) -> T {
+        selector(&self.inner.borrow().state)
+    }
+
+    /// Stream of values returned by `selector`, it yields the new value every
+    /// time the selected value changes after dispatching an action.
+    ///
+    /// Elements usually use [`OrdersExt::subscribe_store`] instead of this
+    /// method.
+    ///
+    /// [`OrdersExt::subscribe_store`]: crate::prelude::OrdersExt::subscribe_store
+    pub fn changes<T>(&self, selector: impl Fn(&S) -> T + 'static) -> impl Stream<Item = T>
+    where
+        T: Clone + PartialEq + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded();
+        let mut inner = self.inner.borrow_mut();
+        let mut last = selector(&inner.state);
+        inner.subscribers.push(Box::new(move |state| {
+            let val = selector(state);
+            if val == last {
+                return !sender.is_closed();
+            }
+            last = val.clone();
+            sender.unbounded_send(val).is_ok()
+        }));
+        receiver
+    }
+}
+
+impl Env {
+    /// Get store of state `S` inserted in this branch or its predecessors
+    ///
+    /// # Panics
+    ///
+    /// This panics if there is no store for `S` in the environment, you can
+    /// use `try_store` for non-panicing version.
+    pub fn store<S: Reducer>(&self) -> Store<S> {
+        self.try_store()
+            .unwrap_or_else(|| panic!("{} isn't found in the environment", type_name::<Store<S>>()))
+    }
+
+    /// Same as `store` but returns `None` if there is no store for `S`
+    pub fn try_store<S: Reducer>(&self) -> Option<Store<S>> {
+        self.get::<Store<S>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seed::futures::{FutureExt, StreamExt};
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+        clicks: u32,
+    }
+
+    enum Action {
+        Add(i32),
+        Click,
+    }
+
+    impl Reducer for Counter {
+        type Action = Action;
+
+        fn reduce(&mut self, action: Action) {
+            match action {
+                Action::Add(val) => self.value += val,
+                Action::Click => self.clicks += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let env = Env::base_branch().insert(Store::new(Counter::default()));
+        let store = env.branch().branch().store::<Counter>();
+        let mut values = store.changes(|state| state.value);
+        let mut clicks = store.changes(|state| state.clicks);
+
+        env.store::<Counter>().dispatch(Action::Add(2));
+        assert_eq!(store.state().value, 2);
+        assert_eq!(values.next().now_or_never(), Some(Some(2)));
+        assert_eq!(clicks.next().now_or_never(), None);
+
+        // values that don't change are not yielded
+        store.dispatch(Action::Add(0));
+        store.dispatch(Action::Click);
+        assert_eq!(values.next().now_or_never(), None);
+        assert_eq!(clicks.next().now_or_never(), Some(Some(1)));
+
+        // dropped streams are unsubscribed
+        drop(values);
+        store.dispatch(Action::Add(1));
+        assert_eq!(store.inner.borrow().subscribers.len(), 1);
+        assert!(Env::base_branch().try_store::<Counter>().is_none());
+    }
+}
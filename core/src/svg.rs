@@ -0,0 +1,662 @@
+//! Functions used to create SVG nodes
+//!
+//! SVG elements are created as [`HtmlEl`] in the SVG namespace, each element
+//! provides typed methods for its own attributes, and shape elements provide
+//! presentation attributes (e.g. `fill` and `stroke`) through
+//! [`PresentationApi`]:
+//!
+//! ```rust
+//! use savory::{
+//!     prelude::*,
+//!     svg::{self, Paint, PathData, ViewBox},
+//! };
+//! use savory_style::Color;
+//!
+//! let check: Node<()> = svg::svg()
+//!     .view_box(ViewBox::new(0.0, 0.0, 24.0, 24.0))
+//!     .push(
+//!         svg::path()
+//!             .d(PathData::new().move_to(4.0, 12.0).line_to(10.0, 18.0).line_to(20.0, 6.0))
+//!             .fill(Paint::None)
+//!             .stroke(Color::Black)
+//!             .stroke_width(2.0),
+//!     )
+//!     .into();
+//!
+//! assert_eq!(
+//!     check.to_html(),
+//!     concat!(
+//!         r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">"#,
+//!         r#"<path d="M 4 12 L 10 18 L 20 6" fill="none" stroke="black" stroke-width="2"/>"#,
+//!         r#"</svg>"#,
+//!     )
+//! );
+//! ```
+//!
+//! SVG markup (e.g. icons exported from design tools) can be converted into
+//! nodes using [`parse`].
+
+use crate::{
+    html::{attr_values, create_typed_html_elements, typed_attr_fns, AttrValueError, HtmlEl},
+    prelude::*,
+};
+use paste::paste;
+use seed::prelude::{El, Node, Tag};
+use std::{convert::Infallible, fmt, str::FromStr};
+
+create_typed_html_elements! {
+    svg => Svg(svg!()) {
+        "viewBox": view_box: ViewBox,
+        "width": width: String,
+        "height": height: String,
+        "x": x: String,
+        "y": y: String,
+        "preserveAspectRatio": preserve_aspect_ratio: String,
+    }
+    path => Path(path!()) {
+        "d": d: PathData,
+        "pathLength": path_length: f64,
+    }
+    circle => Circle(circle!()) {
+        "cx": cx: f64,
+        "cy": cy: f64,
+        "r": r: f64,
+    }
+    rect => Rect(rect!()) {
+        "x": x: f64,
+        "y": y: f64,
+        "width": width: f64,
+        "height": height: f64,
+        "rx": rx: f64,
+        "ry": ry: f64,
+    }
+    g => G(g!()) {}
+    line => Line(line_!()) {
+        "x1": x1: f64,
+        "y1": y1: f64,
+        "x2": x2: f64,
+        "y2": y2: f64,
+    }
+    polyline => Polyline(polyline!()) {}
+    defs => Defs(defs!()) {}
+    linear_gradient => LinearGradient(linearGradient!()) {
+        "x1": x1: String,
+        "y1": y1: String,
+        "x2": x2: String,
+        "y2": y2: String,
+        "gradientUnits": gradient_units: GradientUnits,
+        "gradientTransform": gradient_transform: String,
+        "spreadMethod": spread_method: SpreadMethod,
+        "href": href: String,
+    }
+    stop => Stop(stop!()) {
+        "offset": offset: f64,
+    }
+}
+
+impl<Msg> HtmlEl<tags::Polyline, Msg> {
+    pub fn points(self, points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let points = points
+            .into_iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.set_attr("points", points)
+    }
+}
+
+impl<Msg> HtmlEl<tags::Stop, Msg> {
+    pub fn stop_color(self, val: impl Into<Paint>) -> Self {
+        self.set_attr("stop-color", val.into())
+    }
+
+    pub fn stop_opacity(self, val: f64) -> Self {
+        self.set_attr("stop-opacity", val)
+    }
+}
+
+/// Marker for SVG elements that accept presentation attributes
+pub trait SvgTag {}
+
+impl SvgTag for tags::Svg {}
+impl SvgTag for tags::Path {}
+impl SvgTag for tags::Circle {}
+impl SvgTag for tags::Rect {}
+impl SvgTag for tags::G {}
+impl SvgTag for tags::Line {}
+impl SvgTag for tags::Polyline {}
+
+/// SVG presentation attributes
+pub trait PresentationApi: AttributeApi {
+    fn fill(self, val: impl Into<Paint>) -> Self {
+        self.set_attr("fill", val.into())
+    }
+
+    fn fill_opacity(self, val: f64) -> Self {
+        self.set_attr("fill-opacity", val)
+    }
+
+    fn fill_rule(self, val: FillRule) -> Self {
+        self.set_attr("fill-rule", val)
+    }
+
+    fn stroke(self, val: impl Into<Paint>) -> Self {
+        self.set_attr("stroke", val.into())
+    }
+
+    fn stroke_width(self, val: f64) -> Self {
+        self.set_attr("stroke-width", val)
+    }
+
+    fn stroke_opacity(self, val: f64) -> Self {
+        self.set_attr("stroke-opacity", val)
+    }
+
+    fn stroke_linecap(self, val: LineCap) -> Self {
+        self.set_attr("stroke-linecap", val)
+    }
+
+    fn stroke_linejoin(self, val: LineJoin) -> Self {
+        self.set_attr("stroke-linejoin", val)
+    }
+
+    fn opacity(self, val: f64) -> Self {
+        self.set_attr("opacity", val)
+    }
+
+    /// Transform list, e.g. `rotate(45 12 12)`
+    fn transform(self, val: impl ToString) -> Self {
+        self.set_attr("transform", val)
+    }
+}
+
+impl<T: SvgTag, Msg> PresentationApi for HtmlEl<T, Msg> {}
+
+/// Value of `fill`, `stroke` and `stop-color` attributes
+///
+/// `savory_style::Color` can be converted into `Paint`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    None,
+    CurrentColor,
+    Color(String),
+    /// Paint server (e.g. `linearGradient`) with the given id
+    Url(String),
+}
+
+impl Paint {
+    pub fn url(id: impl ToString) -> Self {
+        Paint::Url(id.to_string())
+    }
+}
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Paint::None => f.write_str("none"),
+            Paint::CurrentColor => f.write_str("currentColor"),
+            Paint::Color(color) => f.write_str(color),
+            Paint::Url(id) => write!(f, "url(#{})", id),
+        }
+    }
+}
+
+/// Value of `viewBox` attribute
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ViewBox {
+    pub fn new(min_x: f64, min_y: f64, width: f64, height: f64) -> Self {
+        Self {
+            min_x,
+            min_y,
+            width,
+            height,
+        }
+    }
+}
+
+impl fmt::Display for ViewBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.min_x, self.min_y, self.width, self.height
+        )
+    }
+}
+
+impl FromStr for ViewBox {
+    type Err = AttrValueError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || AttrValueError {
+            value: value.to_string(),
+            expected: "ViewBox",
+        };
+        let numbers = value
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|num| !num.is_empty())
+            .map(|num| num.parse::<f64>().map_err(|_| error()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match numbers[..] {
+            [min_x, min_y, width, height] => Ok(Self::new(min_x, min_y, width, height)),
+            _ => Err(error()),
+        }
+    }
+}
+
+/// Builder for path data used by `d` attribute
+///
+/// Methods ending with `_to` use absolute coordinates, while methods ending
+/// with `_by` use coordinates relative to the current point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathData(String);
+
+impl PathData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn command(mut self, command: char, args: &[f64]) -> Self {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        self.0.push(command);
+        for arg in args {
+            self.0.push_str(&format!(" {}", arg));
+        }
+        self
+    }
+
+    pub fn move_to(self, x: f64, y: f64) -> Self {
+        self.command('M', &[x, y])
+    }
+
+    pub fn move_by(self, dx: f64, dy: f64) -> Self {
+        self.command('m', &[dx, dy])
+    }
+
+    pub fn line_to(self, x: f64, y: f64) -> Self {
+        self.command('L', &[x, y])
+    }
+
+    pub fn line_by(self, dx: f64, dy: f64) -> Self {
+        self.command('l', &[dx, dy])
+    }
+
+    pub fn horizontal_to(self, x: f64) -> Self {
+        self.command('H', &[x])
+    }
+
+    pub fn horizontal_by(self, dx: f64) -> Self {
+        self.command('h', &[dx])
+    }
+
+    pub fn vertical_to(self, y: f64) -> Self {
+        self.command('V', &[y])
+    }
+
+    pub fn vertical_by(self, dy: f64) -> Self {
+        self.command('v', &[dy])
+    }
+
+    /// Cubic Bézier curve to `(x, y)` using `(x1, y1)` and `(x2, y2)` as
+    /// control points
+    pub fn curve_to(self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) -> Self {
+        self.command('C', &[x1, y1, x2, y2, x, y])
+    }
+
+    pub fn curve_by(self, dx1: f64, dy1: f64, dx2: f64, dy2: f64, dx: f64, dy: f64) -> Self {
+        self.command('c', &[dx1, dy1, dx2, dy2, dx, dy])
+    }
+
+    /// Quadratic Bézier curve to `(x, y)` using `(x1, y1)` as control point
+    pub fn quad_to(self, x1: f64, y1: f64, x: f64, y: f64) -> Self {
+        self.command('Q', &[x1, y1, x, y])
+    }
+
+    pub fn quad_by(self, dx1: f64, dy1: f64, dx: f64, dy: f64) -> Self {
+        self.command('q', &[dx1, dy1, dx, dy])
+    }
+
+    /// Elliptical arc to `(x, y)`
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(
+        self,
+        rx: f64,
+        ry: f64,
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    ) -> Self {
+        let flag = |val: bool| if val { 1.0 } else { 0.0 };
+        self.command('A', &[rx, ry, rotation, flag(large_arc), flag(sweep), x, y])
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_by(
+        self,
+        rx: f64,
+        ry: f64,
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        dx: f64,
+        dy: f64,
+    ) -> Self {
+        let flag = |val: bool| if val { 1.0 } else { 0.0 };
+        self.command(
+            'a',
+            &[rx, ry, rotation, flag(large_arc), flag(sweep), dx, dy],
+        )
+    }
+
+    pub fn close(self) -> Self {
+        self.command('Z', &[])
+    }
+}
+
+impl fmt::Display for PathData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for PathData {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(PathData(value.to_string()))
+    }
+}
+
+attr_values! {
+    /// Value of `fill-rule` attribute
+    FillRule {
+        NonZero = "nonzero",
+        EvenOdd = "evenodd",
+    }
+
+    /// Value of `stroke-linecap` attribute
+    LineCap {
+        Butt = "butt",
+        Round = "round",
+        Square = "square",
+    }
+
+    /// Value of `stroke-linejoin` attribute
+    LineJoin {
+        Miter = "miter",
+        MiterClip = "miter-clip",
+        Round = "round",
+        Bevel = "bevel",
+        Arcs = "arcs",
+    }
+
+    /// Value of `gradientUnits` attribute
+    GradientUnits {
+        UserSpaceOnUse = "userSpaceOnUse",
+        ObjectBoundingBox = "objectBoundingBox",
+    }
+
+    /// Value of `spreadMethod` attribute
+    SpreadMethod {
+        Pad = "pad",
+        Reflect = "reflect",
+        Repeat = "repeat",
+    }
+}
+
+/// Error returned when parsing invalid SVG markup
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Byte offset in the source where the error occurred
+    pub position: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.reason, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse SVG markup into nodes in the SVG namespace
+///
+/// Comments, processing instructions and doctype declarations are skipped, as
+/// well as text that only contains whitespace.
+///
+/// ```rust
+/// # use savory::{prelude::*, svg};
+/// let nodes: Vec<Node<()>> = svg::parse(r#"<circle cx="12" cy="12" r="10"/>"#).unwrap();
+/// assert_eq!(
+///     nodes.to_html(),
+///     r#"<circle xmlns="http://www.w3.org/2000/svg" cx="12" cy="12" r="10"/>"#
+/// );
+///
+/// assert!(svg::parse::<()>("<g><path></g>").is_err());
+/// ```
+pub fn parse<Msg>(source: &str) -> Result<Vec<Node<Msg>>, ParseError> {
+    Parser { source, pos: 0 }.children(None)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn error<T>(&self, reason: &'static str) -> Result<T, ParseError> {
+        Err(ParseError {
+            position: self.pos,
+            reason,
+        })
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest().starts_with(prefix);
+        if found {
+            self.pos += prefix.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, prefix: &str, reason: &'static str) -> Result<(), ParseError> {
+        match self.eat(prefix) {
+            true => Ok(()),
+            false => self.error(reason),
+        }
+    }
+
+    /// Consume everything until `end`, returns the consumed text without `end`
+    fn until(&mut self, end: &str, reason: &'static str) -> Result<&'a str, ParseError> {
+        match self.rest().find(end) {
+            Some(len) => {
+                let text = &self.rest()[..len];
+                self.pos += len + end.len();
+                Ok(text)
+            }
+            None => self.error(reason),
+        }
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let len = self.rest().find(|c| !pred(c)).unwrap_or(self.rest().len());
+        let text = &self.rest()[..len];
+        self.pos += len;
+        text
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn name(&mut self) -> &'a str {
+        self.take_while(|c| !c.is_whitespace() && !matches!(c, '/' | '>' | '=' | '<'))
+    }
+
+    fn children<Msg>(&mut self, parent: Option<&str>) -> Result<Vec<Node<Msg>>, ParseError> {
+        let mut nodes = vec![];
+        loop {
+            if self.rest().is_empty() {
+                return match parent {
+                    Some(_) => self.error("unclosed element"),
+                    None => Ok(nodes),
+                };
+            } else if self.eat("</") {
+                let name = self.name();
+                if parent != Some(name) {
+                    return self.error("unexpected closing tag");
+                }
+                self.skip_whitespace();
+                self.expect(">", "expected `>`")?;
+                return Ok(nodes);
+            } else if self.eat("<!--") {
+                self.until("-->", "unclosed comment")?;
+            } else if self.eat("<![CDATA[") {
+                let text = self.until("]]>", "unclosed CDATA section")?;
+                nodes.push(Node::new_text(text.to_string()));
+            } else if self.eat("<?") {
+                self.until("?>", "unclosed processing instruction")?;
+            } else if self.eat("<!") {
+                self.until(">", "unclosed declaration")?;
+            } else if self.eat("<") {
+                nodes.push(Node::Element(self.element()?));
+            } else {
+                let text = self.take_while(|c| c != '<');
+                if !text.trim().is_empty() {
+                    nodes.push(Node::new_text(decode(text)));
+                }
+            }
+        }
+    }
+
+    fn element<Msg>(&mut self) -> Result<El<Msg>, ParseError> {
+        let name = self.name();
+        if name.is_empty() {
+            return self.error("expected element name");
+        }
+        let mut el = El::empty_svg(Tag::from(name.to_string()));
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(el);
+            } else if self.eat(">") {
+                el.children = self.children(Some(name))?;
+                return Ok(el);
+            }
+
+            let attr = self.name();
+            if attr.is_empty() {
+                return self.error("expected attribute name");
+            }
+            self.skip_whitespace();
+            self.expect("=", "expected `=`")?;
+            self.skip_whitespace();
+            let value = match self.rest().chars().next() {
+                Some(quote @ '"') | Some(quote @ '\'') => {
+                    self.pos += 1;
+                    self.until(&quote.to_string(), "unclosed attribute value")?
+                }
+                _ => return self.error("expected quoted attribute value"),
+            };
+            // the namespace is already set by `El::empty_svg`
+            if attr != "xmlns" {
+                el = el.set_attr(attr, decode(value));
+            }
+        }
+    }
+}
+
+/// Decode character references, unknown references are kept as is
+fn decode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let decoded = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                name => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| name.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(std::char::from_u32),
+            };
+            decoded.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let source = r##"<?xml version="1.0"?>
+            <!-- icon -->
+            <svg viewBox="0 0 24 24" xmlns="http://www.w3.org/2000/svg">
+                <linearGradient id='fade'><stop offset="0" stop-color="#fff"/></linearGradient>
+                <text x="2">Tom &amp; Jerry &#x263A;</text>
+            </svg>"##;
+        let nodes = parse::<()>(source).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(
+            nodes.to_html(),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">"#,
+                r##"<linearGradient id="fade"><stop offset="0" stop-color="#fff"/></linearGradient>"##,
+                r#"<text x="2">Tom &amp; Jerry ☺</text>"#,
+                r#"</svg>"#,
+            )
+        );
+
+        let error = parse::<()>("<g>\n<path d=M0></g>").unwrap_err();
+        assert_eq!(error.reason, "expected quoted attribute value");
+        assert_eq!(error.position, 12);
+        assert!(parse::<()>("<g></path>").is_err());
+        assert!(parse::<()>("<g>").is_err());
+    }
+
+    #[test]
+    fn test_view_box() {
+        let view_box = "0, 0 24 24".parse::<ViewBox>().unwrap();
+        assert_eq!(view_box, ViewBox::new(0.0, 0.0, 24.0, 24.0));
+        assert_eq!(view_box.to_string(), "0 0 24 24");
+        assert!("0 0 24".parse::<ViewBox>().is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Virtual clock used by timers of elements driven by [`Harness`].
+//!
+//! Each harness owns its clock and injects it into its [`TestOrders`], timers
+//! created by `OrdersExt::send_after` and `OrdersExt::send_every` while the
+//! harness runs the element don't wait for real time, instead they wait for
+//! the harness clock to reach their deadline, the clock only moves when tests
+//! call [`Harness::advance`].
+//!
+//! [`Harness`]: crate::testing::Harness
+//! [`TestOrders`]: crate::testing::TestOrders
+//! [`Harness::advance`]: crate::testing::Harness::advance
+
+use seed::futures::{FutureExt, Stream, StreamExt};
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll},
+};
+
+thread_local! {
+    // clock of the harness that is running element code right now
+    static CURRENT: RefCell<Option<Clock>> = const { RefCell::new(None) };
+}
+
+type Deadline = Rc<Cell<Option<u64>>>;
+
+/// Virtual clock owned by a harness
+#[derive(Clone, Default)]
+pub(crate) struct Clock(Rc<RefCell<State>>);
+
+#[derive(Default)]
+struct State {
+    now: u64,
+    deadlines: Vec<Weak<Cell<Option<u64>>>>,
+    created: Vec<Timer>,
+}
+
+/// Timers created since the last call to `take_created`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Timer {
+    Timeout(u32),
+    Interval(u32),
+}
+
+/// Clock of the harness that is running element code, if any
+pub(crate) fn current() -> Option<Clock> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Restores the previous current clock when dropped
+struct Enter(Option<Clock>);
+
+impl Drop for Enter {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+impl Clock {
+    /// Run `f` with this clock as the current clock, so timers created by `f`
+    /// wait on it
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        let previous = CURRENT.with(|current| current.borrow_mut().replace(self.clone()));
+        let _enter = Enter(previous);
+        f()
+    }
+
+    pub fn now(&self) -> u64 {
+        self.0.borrow().now
+    }
+
+    pub fn set_now(&self, now: u64) {
+        self.0.borrow_mut().now = now;
+    }
+
+    /// The nearest deadline after the current time and before (or at) `until`
+    pub fn next_deadline(&self, until: u64) -> Option<u64> {
+        let mut state = self.0.borrow_mut();
+        let now = state.now;
+        state
+            .deadlines
+            .retain(|deadline| deadline.strong_count() > 0);
+        state
+            .deadlines
+            .iter()
+            .filter_map(|deadline| deadline.upgrade()?.get())
+            .filter(|deadline| *deadline > now && *deadline <= until)
+            .min()
+    }
+
+    pub fn take_created(&self) -> Vec<Timer> {
+        std::mem::take(&mut self.0.borrow_mut().created)
+    }
+
+    fn register(&self, timer: Timer) -> Deadline {
+        let mut state = self.0.borrow_mut();
+        let ms = match timer {
+            Timer::Timeout(ms) | Timer::Interval(ms) => ms,
+        };
+        let deadline = Rc::new(Cell::new(Some(state.now + ms as u64)));
+        state.deadlines.push(Rc::downgrade(&deadline));
+        state.created.push(timer);
+        deadline
+    }
+
+    /// Same as `seed::prelude::cmds::timeout` but uses this clock
+    pub fn timeout<MsU>(
+        &self,
+        ms: u32,
+        handler: impl FnOnce() -> MsU + Clone + 'static,
+    ) -> impl Future<Output = MsU> {
+        TimeoutFuture {
+            clock: self.clone(),
+            deadline: self.register(Timer::Timeout(ms)),
+        }
+        .map(move |_| handler())
+    }
+
+    /// Same as `seed::prelude::streams::interval` but uses this clock
+    pub fn interval<MsU>(
+        &self,
+        ms: u32,
+        handler: impl FnOnce() -> MsU + Clone + 'static,
+    ) -> impl Stream<Item = MsU> {
+        IntervalStream {
+            clock: self.clone(),
+            ms: ms as u64,
+            deadline: self.register(Timer::Interval(ms)),
+        }
+        .map(move |_| handler.clone()())
+    }
+}
+
+struct TimeoutFuture {
+    clock: Clock,
+    deadline: Deadline,
+}
+
+impl Future for TimeoutFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        match self.deadline.get() {
+            Some(deadline) if deadline > self.clock.now() => Poll::Pending,
+            _ => {
+                self.deadline.set(None);
+                Poll::Ready(())
+            }
+        }
+    }
+}
+
+struct IntervalStream {
+    clock: Clock,
+    ms: u64,
+    deadline: Deadline,
+}
+
+impl Stream for IntervalStream {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<()>> {
+        match self.deadline.get() {
+            Some(deadline) if deadline <= self.clock.now() => {
+                self.deadline.set(Some(deadline + self.ms.max(1)));
+                Poll::Ready(Some(()))
+            }
+            _ => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! Headless harness used to test elements natively using `cargo test`.
+//!
+//! [`Harness`] initialize the element with mock orders ([`TestOrders`]) that
+//! record every call made by the element, messages sent by the element are
+//! dispatched back to it, notifications are delivered to its subscriptions and
+//! timers wait on a virtual clock that only moves when tests advance it, each
+//! harness has its own clock.
+//!
+//! This module is available when the `testing` feature is enabled, usually
+//! in `dev-dependencies`:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! savory = { version = "0.6.0", features = ["testing"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use savory::{prelude::*, testing::{Call, Harness}};
+//!
+//! struct Counter(u32);
+//!
+//! enum Msg {
+//!     Increase,
+//!     IncreaseLater,
+//! }
+//!
+//! impl Element for Counter {
+//!     type Config = u32;
+//!     type Message = Msg;
+//!
+//!     fn init(config: u32, _: &mut impl Orders<Msg>, _: Env) -> Self {
+//!         Counter(config)
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             Msg::Increase => self.0 += 1,
+//!             Msg::IncreaseLater => {
+//!                 orders.send_after(500, || Msg::Increase);
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! impl View<Node<Msg>> for Counter {
+//!     fn view(&self) -> Node<Msg> {
+//!         html::h1().push(self.0.to_string())
+//!     }
+//! }
+//!
+//! let mut counter = Harness::<Counter>::new(0);
+//! counter.send(Msg::Increase).send(Msg::IncreaseLater);
+//! assert_eq!(counter.element().0, 1);
+//! assert_eq!(counter.calls(), vec![Call::SendAfter(500)]);
+//!
+//! counter.advance(499);
+//! assert_eq!(counter.element().0, 1);
+//! counter.advance(1);
+//! assert_eq!(counter.element().0, 2);
+//! assert_eq!(counter.view().to_html(), "<h1>2</h1>");
+//! ```
+
+pub(crate) mod clock;
+mod orders;
+pub mod snapshot;
+
+pub use orders::{Call, TestOrders};
+
+use crate::prelude::*;
+use clock::Clock;
+use orders::State;
+use seed::{app::RenderInfo, futures::task::noop_waker_ref};
+use std::{
+    any::Any,
+    cell::RefCell,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// Harness that drives element update/view cycles without a browser.
+pub struct Harness<E: Element> {
+    element: E,
+    state: Rc<RefCell<State<E::Message>>>,
+    clock: Clock,
+    last_render: Option<u64>,
+}
+
+impl<E: Element> Harness<E> {
+    /// Initialize the element using base environment
+    pub fn new(config: E::Config) -> Self {
+        Self::with_env(config, Env::base_branch())
+    }
+
+    /// Initialize the element using `env`
+    pub fn with_env(config: E::Config, env: Env) -> Self {
+        let mut orders = TestOrders::default();
+        let clock = orders.state.borrow().clock.clone();
+        let element = clock.enter(|| E::mount(config, &mut orders, env));
+        let mut harness = Self {
+            element,
+            state: orders.state,
+            clock,
+            last_render: None,
+        };
+        harness.process();
+        harness
+    }
+
+    /// Element under test
+    pub fn element(&self) -> &E {
+        &self.element
+    }
+
+    /// Mutable reference to the element under test
+    pub fn element_mut(&mut self) -> &mut E {
+        &mut self.element
+    }
+
+    /// Element view
+    pub fn view(&self) -> Node<E::Message>
+    where
+        E: View<Node<E::Message>>,
+    {
+        self.element.view()
+    }
+
+    /// Dispatch `msg` to the element and process everything it causes
+    pub fn send(&mut self, msg: E::Message) -> &mut Self {
+        self.update(msg);
+        self.process();
+        self
+    }
+
+    /// Deliver `message` to the element subscriptions
+    pub fn notify(&mut self, message: impl Any + Clone) -> &mut Self {
+        self.state
+            .borrow_mut()
+            .notifications
+            .push_back(Rc::new(message));
+        self.process();
+        self
+    }
+
+    /// Advance the virtual clock by `ms` milliseconds, firing timers in the
+    /// order of their deadlines
+    pub fn advance(&mut self, ms: u32) -> &mut Self {
+        let until = self.clock.now() + ms as u64;
+        while let Some(deadline) = self.clock.next_deadline(until) {
+            self.clock.set_now(deadline);
+            self.process();
+        }
+        self.clock.set_now(until);
+        self.process();
+        self
+    }
+
+    /// Process pending messages, commands and streams, this is useful after
+    /// changing the element `Env` outside the harness
+    pub fn settle(&mut self) -> &mut Self {
+        self.process();
+        self
+    }
+
+    /// Current time of the virtual clock in milliseconds
+    pub fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    /// Send `Lifecycle::BeforeUnmount` message to the element, as parents do
+    /// before dropping it
+    pub fn unmount(&mut self) -> &mut Self {
+        let mut orders = TestOrders {
+            state: Rc::clone(&self.state),
+        };
+        let element = &mut self.element;
+        self.clock.enter(|| element.unmount(&mut orders));
+        self.process();
+        self
+    }
+
+    /// Simulate rendering, this calls `after_next_render` callbacks and sends
+    /// `Lifecycle::Mounted` message on the first render
+    pub fn render(&mut self) -> &mut Self {
+        let now = self.clock.now();
+        let last_render = self.last_render;
+        let callbacks = std::mem::take(&mut self.state.borrow_mut().after_next_render);
+        let messages = self.clock.enter(|| {
+            callbacks
+                .into_iter()
+                .filter_map(|callback| {
+                    callback(RenderInfo {
+                        timestamp: now as f64,
+                        timestamp_delta: last_render.map(|last| (now - last) as f64),
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+        self.last_render = Some(now);
+        self.state.borrow_mut().messages.extend(messages);
+        self.process();
+        self
+    }
+
+    /// Calls made by the element so far
+    pub fn calls(&self) -> Vec<Call> {
+        self.state.borrow().calls.clone()
+    }
+
+    /// Same as `calls` but clears the recorded calls
+    pub fn take_calls(&mut self) -> Vec<Call> {
+        std::mem::take(&mut self.state.borrow_mut().calls)
+    }
+
+    /// Notifications of type `T` sent by the element so far
+    pub fn notifications<T: Clone + 'static>(&self) -> Vec<T> {
+        self.state
+            .borrow()
+            .notified
+            .iter()
+            .filter_map(|msg| msg.downcast_ref::<T>().cloned())
+            .collect()
+    }
+
+    fn update(&mut self, msg: E::Message) {
+        let mut orders = TestOrders {
+            state: Rc::clone(&self.state),
+        };
+        let element = &mut self.element;
+        self.clock.enter(|| element.update(msg, &mut orders));
+    }
+
+    /// Process pending notifications, commands, streams and messages until
+    /// there is nothing left to do
+    fn process(&mut self) {
+        let mut cx = Context::from_waker(noop_waker_ref());
+        loop {
+            let mut progressed = false;
+
+            // deliver notifications
+            loop {
+                let mut state = self.state.borrow_mut();
+                let message = match state.notifications.pop_front() {
+                    Some(message) => message,
+                    None => break,
+                };
+                let type_id = (*message).type_id();
+                let handlers = state
+                    .subscriptions
+                    .iter()
+                    .filter(|(id, _)| *id == type_id)
+                    .map(|(_, handler)| Rc::clone(handler))
+                    .collect::<Vec<_>>();
+                drop(state);
+                let messages = handlers
+                    .into_iter()
+                    .filter_map(|handler| handler(&*message))
+                    .collect::<Vec<_>>();
+                self.state.borrow_mut().messages.extend(messages);
+                progressed = true;
+            }
+
+            // poll commands
+            let cmds = std::mem::take(&mut self.state.borrow_mut().cmds);
+            let mut pending = vec![];
+            for mut cmd in cmds {
+                match cmd.as_mut().poll(&mut cx) {
+                    Poll::Ready(msg) => {
+                        self.state.borrow_mut().messages.extend(msg);
+                        progressed = true;
+                    }
+                    Poll::Pending => pending.push(cmd),
+                }
+            }
+            let mut state = self.state.borrow_mut();
+            pending.append(&mut state.cmds);
+            state.cmds = pending;
+            drop(state);
+
+            // poll streams
+            let streams = std::mem::take(&mut self.state.borrow_mut().streams);
+            let mut alive = vec![];
+            for mut stream in streams {
+                match stream.as_mut().poll_next(&mut cx) {
+                    Poll::Ready(Some(msg)) => {
+                        self.state.borrow_mut().messages.extend(msg);
+                        alive.push(stream);
+                        progressed = true;
+                    }
+                    Poll::Ready(None) => progressed = true,
+                    Poll::Pending => alive.push(stream),
+                }
+            }
+            let mut state = self.state.borrow_mut();
+            alive.append(&mut state.streams);
+            state.streams = alive;
+            drop(state);
+
+            // dispatch messages
+            loop {
+                let msg = self.state.borrow_mut().messages.pop_front();
+                match msg {
+                    Some(msg) => {
+                        self.update(msg);
+                        progressed = true;
+                    }
+                    None => break,
+                }
+            }
+
+            if !progressed {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::TaskHandle;
+    use std::any::type_name;
+
+    struct Timer {
+        fired: u32,
+        task: Option<TaskHandle>,
+    }
+
+    enum Msg {
+        Start,
+        StartTask,
+        Cancel,
+        Fired,
+    }
+
+    impl Element for Timer {
+        type Config = ();
+        type Message = Msg;
+
+        fn init(_: (), _: &mut impl Orders<Msg>, _: Env) -> Self {
+            Timer {
+                fired: 0,
+                task: None,
+            }
+        }
+
+        fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) {
+            match msg {
+                Msg::Start => {
+                    orders.send_after(100, || Msg::Fired);
+                }
+                Msg::StartTask => {
+                    self.task = Some(
+                        orders.cmd_with_handle(
+                            clock::current()
+                                .expect("harness clock")
+                                .timeout(100, || Msg::Fired),
+                        ),
+                    );
+                }
+                Msg::Cancel => self.task = None,
+                Msg::Fired => self.fired += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn test_harness_clocks() {
+        let mut first = Harness::<Timer>::new(());
+        let mut second = Harness::<Timer>::new(());
+        first.send(Msg::Start);
+        second.send(Msg::Start);
+
+        first.advance(100);
+        assert_eq!(first.element().fired, 1);
+        assert_eq!(second.element().fired, 0);
+        assert_eq!(second.now(), 0);
+
+        second.advance(100);
+        assert_eq!(second.element().fired, 1);
+    }
+
+    #[test]
+    fn test_task_handle() {
+        let mut timer = Harness::<Timer>::new(());
+        timer.send(Msg::StartTask).advance(100);
+        assert_eq!(timer.element().fired, 1);
+
+        timer
+            .send(Msg::StartTask)
+            .advance(50)
+            .send(Msg::Cancel)
+            .advance(50);
+        assert_eq!(timer.element().fired, 1);
+    }
+
+    struct Parent(Timer);
+
+    enum ParentMsg {
+        Timer(Msg),
+    }
+
+    impl Element for Parent {
+        type Config = ();
+        type Message = ParentMsg;
+
+        fn init(_: (), orders: &mut impl Orders<ParentMsg>, env: Env) -> Self {
+            Parent(Timer::init((), &mut orders.proxy(ParentMsg::Timer), env))
+        }
+
+        fn update(&mut self, msg: ParentMsg, orders: &mut impl Orders<ParentMsg>) {
+            match msg {
+                ParentMsg::Timer(msg) => self.0.update(msg, &mut orders.proxy(ParentMsg::Timer)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_proxy() {
+        let mut parent = Harness::<Parent>::new(());
+        parent.take_calls();
+        parent.send(ParentMsg::Timer(Msg::Start));
+        assert_eq!(
+            parent.take_calls(),
+            vec![Call::Proxy(type_name::<Msg>()), Call::SendAfter(100)]
+        );
+
+        parent.advance(100);
+        assert_eq!(parent.element().0.fired, 1);
+        assert_eq!(parent.take_calls(), vec![Call::Proxy(type_name::<Msg>())]);
+    }
+}
@@ -0,0 +1,230 @@
+use super::clock::{Clock, Timer};
+use crate::{
+    orders::{into_msg, MappedOrders},
+    prelude::*,
+};
+use seed::{
+    app::RenderInfo,
+    futures::{FutureExt, Stream, StreamExt},
+};
+use std::{
+    any::{type_name, Any, TypeId},
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+
+/// Calls made by elements on [`TestOrders`]
+///
+/// Calls that take types (e.g. `subscribe`) carry the type name as returned by
+/// `std::any::type_name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Call {
+    /// `send_msg` or `send` was called
+    Send,
+    /// `notify` was called with the notification type
+    Notify(&'static str),
+    /// `subscribe` was called with the subscription type
+    Subscribe(&'static str),
+    /// `perform_cmd` or `cmd` was called
+    Cmd,
+    /// `send_after` was called with the delay in milliseconds
+    SendAfter(u32),
+    /// `stream` was called
+    Stream,
+    /// `send_every` was called with the interval in milliseconds
+    SendEvery(u32),
+    /// `proxy` was called with the child message type
+    Proxy(&'static str),
+    /// `after_next_render` was called
+    AfterNextRender,
+    /// `render` was called
+    Render,
+    /// `force_render_now` was called
+    ForceRenderNow,
+    /// `skip` was called
+    Skip,
+}
+
+type Handler<Ms> = Rc<dyn Fn(&dyn Any) -> Option<Ms>>;
+
+pub(crate) struct State<Ms> {
+    pub calls: Vec<Call>,
+    pub messages: VecDeque<Ms>,
+    pub notifications: VecDeque<Rc<dyn Any>>,
+    pub notified: Vec<Rc<dyn Any>>,
+    pub subscriptions: Vec<(TypeId, Handler<Ms>)>,
+    pub cmds: Vec<Pin<Box<dyn Future<Output = Option<Ms>>>>>,
+    pub streams: Vec<Pin<Box<dyn Stream<Item = Option<Ms>>>>>,
+    pub after_next_render: Vec<Box<dyn FnOnce(RenderInfo) -> Option<Ms>>>,
+    pub clock: Clock,
+}
+
+impl<Ms> Default for State<Ms> {
+    fn default() -> Self {
+        Self {
+            calls: vec![],
+            messages: VecDeque::new(),
+            notifications: VecDeque::new(),
+            notified: vec![],
+            subscriptions: vec![],
+            cmds: vec![],
+            streams: vec![],
+            after_next_render: vec![],
+            clock: Clock::default(),
+        }
+    }
+}
+
+/// Mock `Orders` that records calls made on it instead of talking to Seed
+/// runtime.
+///
+/// `TestOrders` is created and driven by [`Harness`], messages, notifications,
+/// commands and streams are kept in the harness and processed when tests
+/// dispatch messages or advance the virtual clock. Each harness injects its
+/// own virtual clock into its orders, so timers of different harnesses don't
+/// affect each other.
+///
+/// `proxy` returns orders that record their calls in the same `TestOrders`
+/// and map child messages into `Ms`, so child elements can be tested through
+/// their parents.
+///
+/// [`Harness`]: crate::testing::Harness
+pub struct TestOrders<Ms: 'static> {
+    pub(crate) state: Rc<RefCell<State<Ms>>>,
+}
+
+/// Standalone orders, useful to call methods that take orders in unit tests
+impl<Ms: 'static> Default for TestOrders<Ms> {
+    fn default() -> Self {
+        Self {
+            state: Rc::default(),
+        }
+    }
+}
+
+impl<Ms: 'static> TestOrders<Ms> {
+    fn record(&mut self, call: Call) {
+        self.state.borrow_mut().calls.push(call);
+    }
+
+    /// Record `call`, unless the last created timer says it's a timer call
+    fn record_timer(&mut self, call: Call) {
+        let created = self.state.borrow().clock.take_created();
+        let call = match (created.as_slice(), call) {
+            ([Timer::Timeout(ms)], Call::Cmd) => Call::SendAfter(*ms),
+            ([Timer::Interval(ms)], Call::Stream) => Call::SendEvery(*ms),
+            (_, call) => call,
+        };
+        self.record(call);
+    }
+}
+
+impl<Ms: 'static> Orders<Ms> for TestOrders<Ms> {
+    fn proxy<ChildMs: 'static>(
+        &mut self,
+        f: impl FnOnce(ChildMs) -> Ms + Clone + 'static,
+    ) -> MappedOrders<'_, Self, Ms, ChildMs> {
+        self.record(Call::Proxy(type_name::<ChildMs>()));
+        MappedOrders::new(self, f)
+    }
+
+    fn render(&mut self) -> &mut Self {
+        self.record(Call::Render);
+        self
+    }
+
+    fn force_render_now(&mut self) -> &mut Self {
+        self.record(Call::ForceRenderNow);
+        self
+    }
+
+    fn skip(&mut self) -> &mut Self {
+        self.record(Call::Skip);
+        self
+    }
+
+    fn notify(&mut self, message: impl Any + Clone) -> &mut Self {
+        self.record(Call::Notify(type_name_of(&message)));
+        let message: Rc<dyn Any> = Rc::new(message);
+        let mut state = self.state.borrow_mut();
+        state.notified.push(Rc::clone(&message));
+        state.notifications.push_back(message);
+        drop(state);
+        self
+    }
+
+    fn send_msg(&mut self, msg: Ms) -> &mut Self {
+        self.record(Call::Send);
+        self.state.borrow_mut().messages.push_back(msg);
+        self
+    }
+
+    fn perform_cmd<MsU: 'static>(&mut self, cmd: impl Future<Output = MsU> + 'static) -> &mut Self {
+        self.record_timer(Call::Cmd);
+        self.state
+            .borrow_mut()
+            .cmds
+            .push(Box::pin(cmd.map(into_msg::<Ms, MsU>)));
+        self
+    }
+
+    fn msg_sender(&self) -> Rc<dyn Fn(Option<Ms>)> {
+        let state = Rc::clone(&self.state);
+        Rc::new(move |msg| {
+            if let Some(msg) = msg {
+                state.borrow_mut().messages.push_back(msg);
+            }
+        })
+    }
+
+    fn after_next_render<MsU: 'static>(
+        &mut self,
+        callback: impl FnOnce(RenderInfo) -> MsU + 'static,
+    ) -> &mut Self {
+        self.record(Call::AfterNextRender);
+        self.state
+            .borrow_mut()
+            .after_next_render
+            .push(Box::new(move |info| into_msg(callback(info))));
+        self
+    }
+
+    fn subscribe<MsU: 'static, SubMs: 'static + Clone>(
+        &mut self,
+        handler: impl FnOnce(SubMs) -> MsU + Clone + 'static,
+    ) -> &mut Self {
+        self.record(Call::Subscribe(type_name::<SubMs>()));
+        let handler: Handler<Ms> = Rc::new(move |message: &dyn Any| {
+            let message = message
+                .downcast_ref::<SubMs>()
+                .expect("subscriptions are matched by TypeId")
+                .clone();
+            into_msg(handler.clone()(message))
+        });
+        self.state
+            .borrow_mut()
+            .subscriptions
+            .push((TypeId::of::<SubMs>(), handler));
+        self
+    }
+
+    fn stream<MsU: 'static>(&mut self, stream: impl Stream<Item = MsU> + 'static) -> &mut Self {
+        self.record_timer(Call::Stream);
+        self.state
+            .borrow_mut()
+            .streams
+            .push(Box::pin(stream.map(into_msg::<Ms, MsU>)));
+        self
+    }
+
+    fn clone_base_path(&self) -> Rc<Vec<String>> {
+        Rc::new(vec![])
+    }
+}
+
+fn type_name_of<T>(_: &T) -> &'static str {
+    type_name::<T>()
+}
@@ -0,0 +1,236 @@
+//! Snapshot testing for views.
+//!
+//! Snapshots are normalized HTML, attributes are sorted by name and every
+//! element is printed on its own line, so diffs between snapshots stay small
+//! and readable in review.
+//!
+//! Snapshots are stored as `.snap` files and compared with the view, missing
+//! snapshots and snapshots that doesn't match fail the test. Set the
+//! `SAVORY_UPDATE_SNAPSHOTS` environment variable to write missing snapshots
+//! and overwrite the ones that doesn't match. Snapshots are never written when
+//! the `CI` environment variable is set, so CI fails on missing snapshots even
+//! if `SAVORY_UPDATE_SNAPSHOTS` is set.
+//!
+//! ```rust,no_run
+//! use savory::{prelude::*, assert_snapshot};
+//!
+//! let node: Node<()> = html::div().class("card").push("Hello");
+//! // compares the node with `tests/snapshots/card.snap`
+//! assert_snapshot!("card", node);
+//! ```
+
+use crate::{
+    prelude::*,
+    render::{escape_into, style_attr, RAW_TEXT_ELEMENTS, VOID_ELEMENTS},
+};
+use seed::{browser::dom::Namespace, prelude::AtValue};
+use std::{fs, path::Path};
+
+/// Environment variable used to overwrite mismatched snapshots
+pub const UPDATE_SNAPSHOTS_VAR: &str = "SAVORY_UPDATE_SNAPSHOTS";
+
+/// Environment variable set by CI services, snapshots are never written when
+/// it's set
+pub const CI_VAR: &str = "CI";
+
+/// Compare `node` with the snapshot file `tests/snapshots/<name>.snap` in the
+/// current crate
+///
+/// See [module docs](crate::testing::snapshot) for more details.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $node:expr $(,)?) => {
+        $crate::testing::snapshot::assert_snapshot(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"),
+            $name,
+            &$node,
+        )
+    };
+}
+
+/// Compare `node` with the snapshot file `<dir>/<name>.snap`
+///
+/// # Panics
+/// This panics if the snapshot is missing or doesn't match `node`, unless
+/// `SAVORY_UPDATE_SNAPSHOTS` is set (and `CI` isn't), in that case the
+/// snapshot get written.
+pub fn assert_snapshot<Msg>(dir: impl AsRef<Path>, name: &str, node: &Node<Msg>) {
+    let update =
+        std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() && std::env::var_os(CI_VAR).is_none();
+    check_snapshot(dir.as_ref(), name, &to_snapshot(node), update);
+}
+
+fn check_snapshot(dir: &Path, name: &str, snapshot: &str, update: bool) {
+    let path = dir.join(format!("{}.snap", name));
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing == snapshot => {}
+        Ok(existing) if !update => panic!(
+            "snapshot `{}` doesn't match, set `{}` to update it\n\n--- {}\n{}\n+++ new\n{}",
+            name,
+            UPDATE_SNAPSHOTS_VAR,
+            path.display(),
+            existing,
+            snapshot
+        ),
+        Err(_) if !update => panic!(
+            "snapshot `{}` is missing, set `{}` to write it\n\n--- {}\n+++ new\n{}",
+            name,
+            UPDATE_SNAPSHOTS_VAR,
+            path.display(),
+            snapshot
+        ),
+        _ => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).expect("create snapshots directory");
+            }
+            fs::write(&path, snapshot).expect("write snapshot file");
+        }
+    }
+}
+
+/// Render `node` into normalized HTML, attributes are sorted and elements are
+/// indented by two spaces
+///
+/// ```rust
+/// # use savory::{prelude::*, testing::snapshot::to_snapshot};
+/// let node: Node<()> = html::div()
+///     .id("card")
+///     .class("card")
+///     .push(html::h1().push("Title"))
+///     .push(html::br());
+///
+/// assert_eq!(
+///     to_snapshot(&node),
+///     "<div class=\"card\" id=\"card\">\n  <h1>Title</h1>\n  <br>\n</div>\n"
+/// );
+/// ```
+pub fn to_snapshot<Msg>(node: &Node<Msg>) -> String {
+    let mut out = String::new();
+    write_node(node, None, 0, &mut out);
+    out
+}
+
+fn indent(depth: usize, out: &mut String) {
+    (0..depth).for_each(|_| out.push_str("  "));
+}
+
+fn write_node<Msg>(
+    node: &Node<Msg>,
+    parent_ns: Option<&Namespace>,
+    depth: usize,
+    out: &mut String,
+) {
+    match node {
+        Node::Element(el) => write_el(el, parent_ns, depth, out),
+        Node::Text(text) if !text.text.trim().is_empty() => {
+            indent(depth, out);
+            escape_into(text.text.trim(), false, out);
+            out.push('\n');
+        }
+        Node::Text(_) | Node::Empty | Node::NoChange => {}
+    }
+}
+
+fn write_el<Msg>(el: &El<Msg>, parent_ns: Option<&Namespace>, depth: usize, out: &mut String) {
+    let tag = el.tag.as_str();
+    let namespace = el.namespace.as_ref().filter(|ns| **ns != Namespace::Html);
+
+    let mut attrs = el
+        .attrs
+        .vals
+        .iter()
+        .filter(|(name, _)| name.as_str() != "style")
+        .filter_map(|(name, value)| match value {
+            AtValue::Some(value) => Some((name.as_str().to_string(), Some(value.clone()))),
+            AtValue::None => Some((name.as_str().to_string(), None)),
+            AtValue::Ignored => None,
+        })
+        .collect::<Vec<_>>();
+    if let Some(style) = style_attr(el) {
+        attrs.push(("style".into(), Some(style)));
+    }
+    if namespace.is_some() && namespace != parent_ns {
+        attrs.push(("xmlns".into(), namespace.map(|ns| ns.as_str().to_string())));
+    }
+    attrs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    indent(depth, out);
+    out.push('<');
+    out.push_str(tag);
+    for (name, value) in attrs {
+        out.push(' ');
+        out.push_str(&name);
+        if let Some(value) = value {
+            out.push_str("=\"");
+            escape_into(&value, true, out);
+            out.push('"');
+        }
+    }
+
+    let tag_lowercase = tag.to_lowercase();
+    let children = el
+        .children
+        .iter()
+        .filter(|child| match child {
+            Node::Element(_) => true,
+            Node::Text(text) => !text.text.trim().is_empty(),
+            Node::Empty | Node::NoChange => false,
+        })
+        .collect::<Vec<_>>();
+
+    if namespace.is_none() && VOID_ELEMENTS.contains(&tag_lowercase.as_str()) {
+        out.push_str(">\n");
+    } else if children.is_empty() {
+        match namespace {
+            Some(_) => out.push_str("/>\n"),
+            None => {
+                out.push_str("></");
+                out.push_str(tag);
+                out.push_str(">\n");
+            }
+        }
+    } else if let [Node::Text(text)] = children.as_slice() {
+        // single text child is printed inline
+        out.push('>');
+        if RAW_TEXT_ELEMENTS.contains(&tag_lowercase.as_str()) {
+            out.push_str(text.text.trim());
+        } else {
+            escape_into(text.text.trim(), false, out);
+        }
+        out.push_str("</");
+        out.push_str(tag);
+        out.push_str(">\n");
+    } else {
+        out.push_str(">\n");
+        for child in children {
+            write_node(child, namespace, depth + 1, out);
+        }
+        indent(depth, out);
+        out.push_str("</");
+        out.push_str(tag);
+        out.push_str(">\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn test_missing_snapshot() {
+        let dir = std::env::temp_dir().join(format!("savory-snapshots-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let missing = catch_unwind(|| check_snapshot(&dir, "card", "<div>\n</div>\n", false));
+        assert!(missing.is_err());
+        assert!(!dir.join("card.snap").exists());
+
+        check_snapshot(&dir, "card", "<div>\n</div>\n", true);
+        check_snapshot(&dir, "card", "<div>\n</div>\n", false);
+        let mismatch = catch_unwind(|| check_snapshot(&dir, "card", "<span>\n</span>\n", false));
+        assert!(mismatch.is_err());
+
+        fs::remove_dir_all(&dir).expect("remove snapshots directory");
+    }
+}
@@ -14,6 +14,47 @@ pub trait PushOwned<T> {
             self
         }
     }
+
+    /// Push `val` if `condition` is `true`
+    ///
+    /// Closures are views too, so they can be used to build the pushed view
+    /// only when it's needed:
+    ///
+    /// ```rust
+    /// # use savory::prelude::*;
+    /// let loading = false;
+    /// let node: Node<()> = html::div()
+    ///     .push_if(loading, || html::span().push("Loading.."))
+    ///     .push_if(!loading, "Done");
+    ///
+    /// assert_eq!(node.to_html(), "<div>Done</div>");
+    /// ```
+    fn push_if(self, condition: bool, val: T) -> Self
+    where
+        Self: Sized,
+    {
+        if condition {
+            self.push(val)
+        } else {
+            self
+        }
+    }
+
+    /// Push every item in `iter`
+    ///
+    /// ```rust
+    /// # use savory::prelude::*;
+    /// let node: Node<()> = html::ul()
+    ///     .push_iter(["one", "two"].iter().map(|item| html::li().push(*item)));
+    ///
+    /// assert_eq!(node.to_html(), "<ul><li>one</li><li>two</li></ul>");
+    /// ```
+    fn push_iter(self, iter: impl IntoIterator<Item = T>) -> Self
+    where
+        Self: Sized,
+    {
+        iter.into_iter().fold(self, |this, val| this.push(val))
+    }
 }
 
 /// Set `T` in `Self` in builder-style
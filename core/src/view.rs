@@ -34,12 +34,36 @@
 //! let node: Node<()> = greeting.view();
 //! ```
 //!
+//! # Conditional views
+//!
+//! Branches that return different view types can be wrapped by [`Either`], or
+//! rendered into nodes using [`match_view!`], which accepts nodes too:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! enum Status {
+//!     Loading,
+//!     Failed(&'static str),
+//!     Loaded(Vec<&'static str>),
+//! }
+//!
+//! let status = Status::Failed("Timeout");
+//! let node: Node<()> = html::div().push(savory::match_view!(status, {
+//!     Status::Loading => "Loading..",
+//!     Status::Failed(err) => html::strong().push(err),
+//!     Status::Loaded(items) => html::ul_with(items.into_iter().map(html::li_with)),
+//! }));
+//!
+//! assert_eq!(node.to_html(), "<div><strong>Timeout</strong></div>");
+//! ```
+//!
 //! # View types and View functions
 //!
 //! View functions are simple to write and read and suitable for application
 //! code, while view types works well with reusable, complex elements.
 //!
 //! [`View`]: crate::prelude::View
+//! [`match_view!`]: crate::match_view
 //! [`Node`]: crate::prelude::Node
 
 use crate::prelude::{html, Node};
@@ -76,3 +100,54 @@ impl<Msg> View<Node<Msg>> for std::borrow::Cow<'static, str> {
         html::text(self.clone())
     }
 }
+
+/// View that is either `A` or `B`
+///
+/// This is useful when a view have two branches that return different types:
+///
+/// ```rust
+/// # use savory::prelude::*;
+/// let user: Option<&'static str> = None;
+/// let greeting = match user {
+///     Some(name) => Either::Left(move || html::strong().push(name)),
+///     None => Either::Right("Guest"),
+/// };
+///
+/// let node: Node<()> = html::p().push(greeting);
+/// assert_eq!(node.to_html(), "<p>Guest</p>");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<Output, A, B> View<Output> for Either<A, B>
+where
+    A: View<Output>,
+    B: View<Output>,
+{
+    fn view(&self) -> Output {
+        match self {
+            Either::Left(a) => a.view(),
+            Either::Right(b) => b.view(),
+        }
+    }
+}
+
+/// Match `value` against the given patterns, and render the matched arm into
+/// `Vec<Node<Msg>>`
+///
+/// Unlike `match` expression, arms can return different types, any type that
+/// implements [`Children`](crate::html::Children) can be used (e.g. views,
+/// nodes and tuples). See [module docs](crate::view) for an example.
+#[macro_export]
+macro_rules! match_view {
+    ( $value:expr, { $( $pat:pat $( if $guard:expr )? => $view:expr ),+ $(,)? } ) => {
+        match $value {
+            $(
+                $pat $( if $guard )? => $crate::html::Children::into_children($view),
+            )+
+        }
+    };
+}
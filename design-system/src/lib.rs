@@ -7,15 +7,17 @@ use savory_style::{
     unit::{px, sec, Length},
     values as val, Color, St, Style,
 };
-use std::rc::Rc;
+use std::{borrow::Cow, rc::Rc};
 
 pub struct SavoryDS {
     default_theme: Theme,
     dark_theme: Theme,
     primary: LinSrgb,
     current_theme: ThemeName,
+    class_prefix: Option<Cow<'static, str>>,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ThemeName {
     Default,
     Dark,
@@ -70,14 +72,33 @@ pub struct Theme {
 
 impl SavoryDS {
     pub fn push_to_environment(self, env: Env) {
+        let class_prefix = self.class_prefix.clone();
         let ds = Rc::new(self);
-        env.insert_designer::<Text>(ds.clone())
+        let env = env
+            .insert_designer::<Text>(ds.clone())
             .insert_designer::<Button>(ds.clone())
             .insert_designer::<Switch>(ds.clone())
             .insert_designer::<Radio>(ds.clone())
             .insert_designer::<TextInput>(ds.clone())
             .insert_designer::<ProgressBar>(ds.clone())
-            .insert_designer::<Slider>(ds.clone());
+            .insert_designer::<Slider>(ds.clone())
+            .insert_designer::<Resource>(ds.clone())
+            .insert_designer::<ScrollBox>(ds.clone());
+        if let Some(prefix) = class_prefix {
+            env.insert_class_prefix(prefix);
+        }
+    }
+
+    pub fn theme(mut self, theme: ThemeName) -> Self {
+        self.current_theme = theme;
+        self
+    }
+
+    /// Prefix added to the classes of the elements, e.g. `sv-` renders buttons
+    /// with `sv-button` class instead of `button`
+    pub fn class_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.class_prefix = Some(prefix.into());
+        self
     }
 
     pub fn current_theme(&self) -> &Theme {
@@ -186,6 +207,7 @@ impl Default for SavoryDS {
             dark_theme,
             primary,
             current_theme: ThemeName::Default,
+            class_prefix: None,
         }
     }
 }
@@ -685,6 +707,76 @@ impl Design<ProgressBar> for SavoryDS {
     }
 }
 
+impl Design<Resource> for SavoryDS {
+    fn design(&self, lens: resource::ResourceLens, _: &Env) -> resource::StyleMap {
+        let theme = self.current_theme();
+
+        let resource = Style::default()
+            .position(val::Relative)
+            .and_transition(|t| t.duration(sec(0.3)).ease())
+            .config_if(lens.refreshing, |c| c.opacity(0.5));
+
+        let loading = Style::default()
+            .and_font(|f| f.size(theme.font_size.clone()))
+            .and_text(|t| {
+                t.color(theme.text_secondary)
+                    .line_height(theme.line_height.clone())
+            })
+            .and_padding(|p| p.y(px(4)));
+
+        let error = Style::default()
+            .and_font(|f| f.size(theme.font_size.clone()))
+            .and_text(|t| t.color(theme.error).line_height(theme.line_height.clone()))
+            .and_padding(|p| p.y(px(4)));
+
+        let retry = Style::default()
+            .and_text(|t| t.color(theme.processing))
+            .background(Color::Transparent)
+            .and_border(|b| b.none())
+            .and_margin(|m| m.left(px(8)))
+            .and_padding(|p| p.zero())
+            .cursor(val::Pointer)
+            .and_font(|f| f.size(theme.font_size.clone()));
+
+        resource::StyleMap {
+            resource,
+            loading,
+            error,
+            retry,
+        }
+    }
+}
+
+impl Design<ScrollBox> for SavoryDS {
+    fn design(&self, lens: scroll_box::ScrollBoxLens, _: &Env) -> scroll_box::StyleMap {
+        let theme = self.current_theme();
+        let shadow = LinSrgba::new(0., 0., 0., 0.15);
+        let thumb = LinSrgba::new(theme.black.red, theme.black.green, theme.black.blue, 0.25);
+
+        // shadows show that there is more content to scroll to
+        let scroll_box = Style::default()
+            .position(val::Relative)
+            .push(St::OverflowY, val::Auto)
+            .try_push(St::MaxHeight, lens.max_height.clone())
+            .push(St::ScrollbarWidth, "thin")
+            .push(
+                St::ScrollbarColor,
+                format!("{} {}", Color::from(thumb), Color::Transparent),
+            )
+            .and_box_shadow(|b| {
+                b.config_if(!lens.at_start, |b| {
+                    b.add(|s| s.inset().y(px(8)).blur(px(8)).spread(px(-8)).color(shadow))
+                })
+                .config_if(!lens.at_end, |b| {
+                    b.add(|s| s.inset().y(px(-8)).blur(px(8)).spread(px(-8)).color(shadow))
+                })
+            })
+            .push(St::Transition, "box-shadow .3s");
+
+        scroll_box::StyleMap { scroll_box }
+    }
+}
+
 pub mod colors {
     use palette::{Hsv, LinSrgb, Mix};
 
@@ -0,0 +1,179 @@
+use savory::{
+    assert_snapshot,
+    prelude::*,
+    testing::{snapshot::to_snapshot, Harness},
+};
+use savory_ds::{SavoryDS, ThemeName};
+use savory_elements::prelude::*;
+use savory_style::unit::px;
+
+fn env(theme: ThemeName) -> Env {
+    let env = Env::base_branch();
+    SavoryDS::default()
+        .theme(theme)
+        .push_to_environment(env.clone());
+    env
+}
+
+fn view<E>(config: E::Config, env: Env) -> Node<E::Message>
+where
+    E: Element + View<Node<<E as Element>::Message>>,
+{
+    Harness::<E>::with_env(config, env).view()
+}
+
+/// Snapshot the view rendered by `view` under every theme
+///
+/// The dark theme only changes the colors of some elements, so dark snapshots
+/// are only kept for views that render differently from the default theme.
+fn assert_themes<Msg>(name: &str, view: impl Fn(Env) -> Node<Msg>) {
+    let default = view(env(ThemeName::Default));
+    assert_snapshot!(&format!("{}-default", name), default);
+
+    let dark = view(env(ThemeName::Dark));
+    if to_snapshot(&dark) != to_snapshot(&default) {
+        assert_snapshot!(&format!("{}-dark", name), dark);
+    }
+}
+
+#[test]
+fn button() {
+    let snapshot = |kind, config: fn() -> button::Config| {
+        assert_themes(&format!("button-{}", kind), |env| {
+            view::<Button>(config(), env)
+        })
+    };
+    snapshot("default", || Button::config().text("Save"));
+    snapshot("suggestion", || Button::config().text("Save").suggestion());
+    snapshot("destructive", || {
+        Button::config().text("Delete").destructive()
+    });
+    snapshot("dashed", || Button::config().text("Save").dashed());
+    snapshot("text", || Button::config().text("Save").text_button());
+    snapshot("link", || Button::config().text("Save").link_button());
+    snapshot("ghost", || Button::config().text("Save").ghost());
+    snapshot("disabled", || Button::config().text("Save").disabled(true));
+}
+
+#[test]
+fn switch() {
+    assert_themes("switch-default", |env| {
+        view::<Switch>(Switch::config().text("Wifi"), env)
+    });
+    assert_themes("switch-toggled", |env| {
+        view::<Switch>(Switch::config().text("Wifi").toggled(true), env)
+    });
+    assert_themes("switch-disabled", |env| {
+        view::<Switch>(Switch::config().text("Wifi").disabled(true), env)
+    });
+}
+
+#[test]
+fn radio() {
+    assert_themes("radio-default", |env| {
+        view::<Radio>(Radio::config().text("Option"), env)
+    });
+    assert_themes("radio-toggled", |env| {
+        view::<Radio>(Radio::config().text("Option").toggled(true), env)
+    });
+    assert_themes("radio-disabled", |env| {
+        view::<Radio>(Radio::config().text("Option").disabled(true), env)
+    });
+}
+
+#[test]
+fn text_input() {
+    assert_themes("text-input-default", |env| {
+        view::<TextInput>(TextInput::config().placeholder("Your name"), env)
+    });
+    assert_themes("text-input-disabled", |env| {
+        view::<TextInput>(TextInput::config().text("Muhannad").disabled(true), env)
+    });
+}
+
+#[test]
+fn slider() {
+    assert_themes("slider-default", |env| {
+        view::<Slider>(Slider::config().value(30.0), env)
+    });
+    assert_themes("slider-disabled", |env| {
+        view::<Slider>(Slider::config().value(30.0).disabled(true), env)
+    });
+}
+
+#[test]
+fn progress_bar() {
+    assert_themes("progress-bar-default", |env| {
+        view::<ProgressBar>(ProgressBar::config().value(40.0), env)
+    });
+    assert_themes("progress-bar-disabled", |env| {
+        view::<ProgressBar>(ProgressBar::config().value(40.0).disabled(true), env)
+    });
+}
+
+#[test]
+fn text() {
+    assert_themes("text-default", |env| -> Node<()> {
+        Text::new("Hello", env).view()
+    });
+    assert_themes("text-bold-italic", |env| -> Node<()> {
+        Text::new("Hello", env).bold_weight().italic().view()
+    });
+}
+
+#[test]
+fn flex() {
+    assert_themes("flex-row", |env| {
+        let flex: Flex<()> = Flex::row()
+            .center()
+            .push(&Text::new("First", env.clone()))
+            .push(&Text::new("Second", env));
+        flex.view()
+    });
+}
+
+#[test]
+fn scroll_box() {
+    assert_themes("scroll-box-default", |env| {
+        view::<ScrollBox>(ScrollBox::config().max_height(px(200)), env)
+    });
+    assert_themes("scroll-box-scrolled", |env| {
+        let mut harness = Harness::<ScrollBox>::with_env(ScrollBox::config(), env);
+        harness.send(scroll_box::Msg::Scrolled(scroll_box::ScrollPosition {
+            top: 100.0,
+            left: 0.0,
+            scroll_height: 1000.0,
+            client_height: 200.0,
+        }));
+        harness.view()
+    });
+}
+
+#[test]
+fn resource() {
+    fn config(
+        fetch: impl Fn() -> Option<Result<String, String>> + 'static,
+    ) -> resource::Config<String, String> {
+        Resource::config(
+            move || {
+                let result = fetch();
+                async move {
+                    match result {
+                        Some(result) => result,
+                        None => std::future::pending().await,
+                    }
+                }
+            },
+            |data: &String| html::p().push(data.clone()),
+        )
+    }
+
+    let snapshot = |kind, fetch: fn() -> Option<Result<String, String>>| {
+        assert_themes(&format!("resource-{}", kind), |env| {
+            view::<Resource<String, String>>(config(fetch), env)
+        })
+    };
+    snapshot("loading", || None);
+    snapshot("failed", || Some(Err("offline".into())));
+    snapshot("ready", || Some(Ok("Muhannad".into())));
+}
@@ -2,7 +2,7 @@
 
 use crate::prelude::*;
 use savory::prelude::Env;
-use std::{ops::Deref, rc::Rc};
+use std::{borrow::Cow, ops::Deref, rc::Rc};
 
 pub trait ViewStyle {
     type StyleMap;
@@ -34,6 +34,14 @@ pub trait Design<T: DataLens + ViewStyle> {
     fn design(&self, lens: <T as DataLens>::Data, env: &Env) -> <T as ViewStyle>::StyleMap;
 }
 
+/// Prefix added to the classes of Savory elements
+///
+/// Design systems insert this into the environment so the classes used by
+/// elements (e.g. `button`) don't collide with the classes used by the host
+/// page, see [`EnvExt::insert_class_prefix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassPrefix(pub Cow<'static, str>);
+
 // // this implementions is from
 // // https://github.com/mdgriffith/style-elements/blob/master/experiments/Aligned/src/Element.elm#L1269
 // /// Calculate screen info based on it's width and height
@@ -47,6 +47,10 @@ pub struct Button {
     #[rich(read)]
     #[element(config)]
     icon: Option<Svg<Msg>>,
+    /// Accessible label, used by buttons that only have an icon
+    #[rich(read)]
+    #[element(config)]
+    label: Option<Cow<'static, str>>,
     #[rich(read(copy, rename = is_disabled))]
     #[element(config(default), data_lens)]
     disabled: bool,
@@ -94,13 +98,16 @@ impl Element for Button {
     type Config = Config;
 
     fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
-        orders.subscribe(|_: RerenderRequested| Msg::Rerender);
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<Button>| Msg::Rerender);
 
         Button {
             id: config.id,
             env,
             text: config.text,
             icon: config.icon,
+            label: config.label,
             disabled: config.disabled,
             focused: false,
             mouse_over: false,
@@ -129,15 +136,16 @@ impl View<Node<Msg>> for Button {
             .designer::<Button>()
             .design(self.data_lens(), &self.env);
         html::button()
-            .class("button")
+            .classes(self.env.classes().add("button"))
             .try_id(self.id.clone())
             .disabled(self.disabled)
+            .try_aria_label(self.label.clone())
             .style(style)
             .on_focus(|_| Msg::Focus(true))
             .on_blur(|_| Msg::Focus(false))
             .on_mouse_over(|_| Msg::MouseOver(true))
             .on_mouse_leave(|_| Msg::MouseOver(false))
-            .try_push(self.icon.as_ref().map(|el| el.view()))
+            .try_push(self.icon.as_ref().map(|el| el.view().aria_hidden(true)))
             .try_push(self.text.clone())
     }
 }
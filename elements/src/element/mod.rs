@@ -3,6 +3,8 @@
 pub mod button;
 pub mod progress_bar;
 pub mod radio;
+pub mod resource;
+pub mod scroll_box;
 pub mod slider;
 pub mod switch;
 pub mod text_input;
@@ -69,7 +69,9 @@ impl Element for ProgressBar {
     type Config = Config;
 
     fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
-        orders.subscribe(|_: RerenderRequested| Msg::Rerender);
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<ProgressBar>| Msg::Rerender);
 
         Self {
             id: config.id,
@@ -97,12 +99,18 @@ impl View<Node<Msg>> for ProgressBar {
             .env
             .designer::<ProgressBar>()
             .design(self.data_lens(), &self.env);
-        let indicator = html::div().class("indicator").style(style_map.indicator);
+        let indicator = html::div()
+            .classes(self.env.classes().add("indicator"))
+            .style(style_map.indicator);
 
         html::div()
             .try_id(self.id.clone())
-            .class("progress-bar")
+            .classes(self.env.classes().add("progress-bar"))
             .style(style_map.progress_bar)
+            .role(Role::ProgressBar)
+            .aria_valuenow(self.value)
+            .aria_valuemin(self.min)
+            .aria_valuemax(self.max)
             .push(indicator)
     }
 }
@@ -34,6 +34,8 @@ pub enum Msg {
 #[element(style_map(radio, check_sign, text))]
 pub struct Radio {
     // general element properties
+    /// Element id, generated by the `IdGenerator` found in the environment
+    /// if it's not set
     #[rich(read)]
     #[element(config)]
     id: Option<Id>,
@@ -66,10 +68,12 @@ impl Element for Radio {
     type Config = Config;
 
     fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
-        orders.subscribe(|_: RerenderRequested| Msg::Rerender);
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<Radio>| Msg::Rerender);
 
         Self {
-            id: config.id,
+            id: config.id.or_else(|| env.try_generate_id("radio")),
             env,
             text: config.text,
             toggled: config.toggled,
@@ -99,24 +103,28 @@ impl View<Node<Msg>> for Radio {
             .designer::<Radio>()
             .design(self.data_lens(), &self.env);
         let radio = html::button()
-            .class("radio")
+            .classes(self.env.classes().add("radio"))
             .style(style_map.radio)
             .disabled(self.disabled)
-            // .checked(self.toggled)
-            // .type_("radio")
+            .role(Role::Radio)
+            .aria_checked(self.toggled)
             .on_focus(|_| Msg::Focus(true))
             .on_blur(|_| Msg::Focus(false))
             .on_mouse_enter(|_| Msg::MouseOver(true))
             .on_mouse_leave(|_| Msg::MouseOver(false))
             .on_click(|_| Msg::Toggle)
             // push check-sign node
-            .set(html::div().class("check-sign").style(style_map.check_sign));
+            .set(
+                html::div()
+                    .classes(self.env.classes().add("check-sign"))
+                    .style(style_map.check_sign),
+            );
 
         match self.text.as_ref() {
             None => radio.try_id(self.id.clone()),
             Some(lbl) => html::label()
                 .try_id(self.id.clone())
-                .class("text")
+                .classes(self.env.classes().add("text"))
                 .style(style_map.text)
                 .push(radio)
                 .push(lbl.clone())
@@ -0,0 +1,457 @@
+//! Resource element
+//!
+//! Resource loads data asynchronously and tracks the loading state for us, it
+//! shows a loading view while the data is loading, an error view with retry
+//! button if loading failed, and the data view once the data is ready.
+//!
+//! See [`Resource`] docs to find out more about its methods.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use savory::prelude::*;
+//! use savory_elements::prelude::*;
+//!
+//! #[derive(Debug)]
+//! pub struct User {
+//!     name: String,
+//! }
+//!
+//! async fn fetch_user() -> Result<User, String> {
+//!     // fetch the user from the server
+//!     Ok(User { name: "Muhannad".into() })
+//! }
+//!
+//! pub enum Msg {
+//!     User(resource::Msg<User, String>),
+//! }
+//!
+//! fn init(orders: &mut impl Orders<Msg>, env: Env) -> Resource<User, String> {
+//!     Resource::config(fetch_user, |user: &User| html::h1().push(user.name.clone()))
+//!         .failed_view(|error: &String| html::p().push(error.clone()))
+//!         .init(&mut orders.proxy(Msg::User), env)
+//! }
+//! ```
+//!
+//! Messages are routed to the resource the same way as other elements, and
+//! parents can send `Msg::Retry` or `Msg::Refresh` to load the data again.
+//!
+//! # Design
+//!
+//! Designers style all resources using the `Resource` type (i.e.
+//! `Resource<(), ()>`), since the style doesn't depend on the data and error
+//! types.
+//!
+//! [`Resource`]: crate::prelude::Resource
+
+use crate::{id::Id, prelude::*};
+use savory::prelude::*;
+use savory_style::prelude::*;
+use std::{borrow::Cow, future::Future, pin::Pin, rc::Rc};
+
+type Fetch<T, E> = Rc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<T, E>>>>>;
+type LoadingView<T, E> = Rc<dyn Fn() -> Node<Msg<T, E>>>;
+type FailedView<T, E> = Rc<dyn Fn(&E) -> Node<Msg<T, E>>>;
+type ReadyView<T, E> = Rc<dyn Fn(&T) -> Node<Msg<T, E>>>;
+
+pub enum Msg<T, E> {
+    Rerender,
+    /// Discard the current state and load the data again
+    Retry,
+    /// Load the data again while keeping the current state visible until the
+    /// new data is loaded
+    Refresh,
+    /// Data finished loading
+    Loaded(Result<T, E>),
+}
+
+/// Loading status of the resource
+#[derive(Debug, Copy, Eq, PartialEq, Clone)]
+pub enum Status {
+    Loading,
+    Failed,
+    Ready,
+}
+
+enum State<T, E> {
+    Loading,
+    Failed(E),
+    Ready(T),
+}
+
+/// Resource element
+///
+/// See the [module docs](crate::prelude::resource)
+pub struct Resource<T = (), E = ()> {
+    id: Option<Id>,
+    env: Env,
+    fetch: Fetch<T, E>,
+    state: State<T, E>,
+    refreshing: bool,
+    // dropping this handle cancels the in-flight request
+    request: Option<TaskHandle>,
+    loading_view: Option<LoadingView<T, E>>,
+    failed_view: Option<FailedView<T, E>>,
+    ready_view: ReadyView<T, E>,
+    loading_text: Cow<'static, str>,
+    failed_text: Cow<'static, str>,
+    retry_text: Cow<'static, str>,
+}
+
+pub struct Config<T, E> {
+    id: Option<Id>,
+    fetch: Fetch<T, E>,
+    loading_text: Cow<'static, str>,
+    failed_text: Cow<'static, str>,
+    retry_text: Cow<'static, str>,
+    loading_view: Option<LoadingView<T, E>>,
+    failed_view: Option<FailedView<T, E>>,
+    ready_view: ReadyView<T, E>,
+}
+
+pub struct ResourceLens {
+    pub status: Status,
+    pub refreshing: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Rich)]
+pub struct StyleMap {
+    #[rich(write(style = compose), write)]
+    pub resource: Style,
+    #[rich(write(style = compose), write)]
+    pub loading: Style,
+    #[rich(write(style = compose), write)]
+    pub error: Style,
+    #[rich(write(style = compose), write)]
+    pub retry: Style,
+}
+
+impl<T, E> ViewStyle for Resource<T, E> {
+    type StyleMap = StyleMap;
+}
+
+impl<T, E> DataLens for Resource<T, E> {
+    type Data = ResourceLens;
+
+    fn data_lens(&self) -> ResourceLens {
+        ResourceLens {
+            status: self.status(),
+            refreshing: self.refreshing,
+        }
+    }
+}
+
+impl<T: 'static, E: 'static> Element for Resource<T, E> {
+    type Message = Msg<T, E>;
+    type Config = Config<T, E>;
+
+    fn init(config: Self::Config, orders: &mut impl Orders<Msg<T, E>>, env: Env) -> Self {
+        orders
+            .subscribe(|_: RerenderRequested| Msg::<T, E>::Rerender)
+            .subscribe_env(&env, |_: Designer<Resource>| Msg::<T, E>::Rerender);
+
+        let mut resource = Self {
+            id: config.id,
+            env,
+            fetch: config.fetch,
+            state: State::Loading,
+            refreshing: false,
+            request: None,
+            loading_view: config.loading_view,
+            failed_view: config.failed_view,
+            ready_view: config.ready_view,
+            loading_text: config.loading_text,
+            failed_text: config.failed_text,
+            retry_text: config.retry_text,
+        };
+        resource.load(orders);
+        resource
+    }
+
+    fn update(&mut self, msg: Msg<T, E>, orders: &mut impl Orders<Msg<T, E>>) {
+        match msg {
+            Msg::Rerender => {}
+            Msg::Retry => {
+                self.state = State::Loading;
+                self.refreshing = false;
+                self.load(orders);
+            }
+            Msg::Refresh => {
+                self.refreshing = true;
+                self.load(orders);
+            }
+            Msg::Loaded(result) => {
+                self.request = None;
+                self.refreshing = false;
+                self.state = match result {
+                    Ok(data) => State::Ready(data),
+                    Err(error) => State::Failed(error),
+                };
+            }
+        }
+    }
+}
+
+impl<T: 'static, E: 'static> Resource<T, E> {
+    pub fn config<F, Fut>(
+        fetch: F,
+        ready_view: impl Fn(&T) -> Node<Msg<T, E>> + 'static,
+    ) -> Config<T, E>
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<T, E>> + 'static,
+    {
+        Config {
+            id: None,
+            fetch: Rc::new(move || Box::pin(fetch())),
+            loading_text: "Loading…".into(),
+            failed_text: "Failed to load".into(),
+            retry_text: "Retry".into(),
+            loading_view: None,
+            failed_view: None,
+            ready_view: Rc::new(ready_view),
+        }
+    }
+
+    fn load(&mut self, orders: &mut impl Orders<Msg<T, E>>) {
+        let request = (self.fetch)();
+        // replacing the handle cancels the previous request
+        self.request = Some(orders.cmd_with_handle(async { Msg::Loaded(request.await) }));
+    }
+}
+
+impl<T, E> Resource<T, E> {
+    pub fn id(&self) -> &Option<Id> {
+        &self.id
+    }
+
+    pub fn status(&self) -> Status {
+        match self.state {
+            State::Loading => Status::Loading,
+            State::Failed(_) => Status::Failed,
+            State::Ready(_) => Status::Ready,
+        }
+    }
+
+    /// Loaded data, if the resource is ready
+    pub fn data(&self) -> Option<&T> {
+        match self.state {
+            State::Ready(ref data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Loading error, if the resource failed to load
+    pub fn error(&self) -> Option<&E> {
+        match self.state {
+            State::Failed(ref error) => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.status() == Status::Loading
+    }
+
+    /// Returns `true` if the resource is loading the data again after `Refresh`
+    pub fn is_refreshing(&self) -> bool {
+        self.refreshing
+    }
+}
+
+impl<T: 'static, E: 'static> View<Node<Msg<T, E>>> for Resource<T, E> {
+    fn view(&self) -> Node<Msg<T, E>> {
+        let style_map = self
+            .env
+            .designer::<Resource>()
+            .design(self.data_lens(), &self.env);
+
+        let content = match self.state {
+            State::Loading => match self.loading_view {
+                Some(ref view) => view(),
+                None => html::div()
+                    .classes(self.env.classes().add("loading"))
+                    .style(style_map.loading)
+                    .push(self.loading_text.clone()),
+            },
+            State::Failed(ref error) => match self.failed_view {
+                Some(ref view) => view(error),
+                None => html::div()
+                    .classes(self.env.classes().add("error"))
+                    .style(style_map.error)
+                    .push(self.failed_text.clone())
+                    .push(
+                        html::button()
+                            .classes(self.env.classes().add("retry"))
+                            .style(style_map.retry)
+                            .on_click(|_| Msg::<T, E>::Retry)
+                            .push(self.retry_text.clone()),
+                    ),
+            },
+            State::Ready(ref data) => (self.ready_view)(data),
+        };
+
+        html::div()
+            .try_id(self.id.clone())
+            .classes(self.env.classes().add("resource"))
+            .style(style_map.resource)
+            .push(content)
+    }
+}
+
+impl<T: 'static, E: 'static> Config<T, E> {
+    pub fn id(mut self, val: impl Into<Id>) -> Self {
+        self.id = Some(val.into());
+        self
+    }
+
+    /// Text shown by the default loading view
+    pub fn loading_text(mut self, val: impl Into<Cow<'static, str>>) -> Self {
+        self.loading_text = val.into();
+        self
+    }
+
+    /// Text shown by the default failed view
+    pub fn failed_text(mut self, val: impl Into<Cow<'static, str>>) -> Self {
+        self.failed_text = val.into();
+        self
+    }
+
+    /// Text of the retry button in the default failed view
+    pub fn retry_text(mut self, val: impl Into<Cow<'static, str>>) -> Self {
+        self.retry_text = val.into();
+        self
+    }
+
+    /// View used while the data is loading
+    pub fn loading_view(mut self, view: impl Fn() -> Node<Msg<T, E>> + 'static) -> Self {
+        self.loading_view = Some(Rc::new(view));
+        self
+    }
+
+    /// View used when loading the data fails
+    pub fn failed_view(mut self, view: impl Fn(&E) -> Node<Msg<T, E>> + 'static) -> Self {
+        self.failed_view = Some(Rc::new(view));
+        self
+    }
+
+    pub fn init(self, orders: &mut impl Orders<Msg<T, E>>, env: Env) -> Resource<T, E> {
+        Resource::mount(self, orders, env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use savory::{
+        seed::futures::channel::oneshot::{self, Sender},
+        testing::Harness,
+    };
+    use std::cell::RefCell;
+
+    type Requests = Rc<RefCell<Vec<Sender<Result<u32, String>>>>>;
+
+    // unstyled designer, so the views can be rendered without a design system
+    struct Plain;
+
+    impl Design<Resource> for Plain {
+        fn design(&self, _: ResourceLens, _: &Env) -> StyleMap {
+            StyleMap::default()
+        }
+    }
+
+    fn harness(config: Config<u32, String>) -> Harness<Resource<u32, String>> {
+        let env = Env::base_branch().insert_designer(Rc::new(Plain) as Rc<dyn Design<Resource>>);
+        Harness::with_env(config, env)
+    }
+
+    // every fetch waits for the test to complete it through `Requests`
+    fn resource(requests: &Requests) -> Config<u32, String> {
+        let requests = Rc::clone(requests);
+        Resource::config(
+            move || {
+                let (sender, receiver) = oneshot::channel();
+                requests.borrow_mut().push(sender);
+                async { receiver.await.unwrap_or_else(|_| Err("canceled".into())) }
+            },
+            |data: &u32| html::p().push(data.to_string()),
+        )
+    }
+
+    fn complete(requests: &Requests, index: usize, result: Result<u32, String>) {
+        let sender = requests.borrow_mut().remove(index);
+        // the receiver is gone if the request was canceled
+        let _ = sender.send(result);
+    }
+
+    #[test]
+    fn test_load() {
+        let requests = Requests::default();
+        let mut harness = harness(resource(&requests));
+        assert!(harness.element().is_loading());
+        assert!(harness.view().to_html().contains("Loading…"));
+
+        complete(&requests, 0, Ok(7));
+        harness.settle();
+        assert_eq!(harness.element().status(), Status::Ready);
+        assert_eq!(harness.element().data(), Some(&7));
+        assert!(harness.view().to_html().contains("<p>7</p>"));
+    }
+
+    #[test]
+    fn test_failure_and_retry() {
+        let requests = Requests::default();
+        let config = resource(&requests)
+            .loading_text("Chargement…")
+            .failed_text("Échec du chargement")
+            .retry_text("Réessayer");
+        let mut harness = harness(config);
+        assert!(harness.view().to_html().contains("Chargement…"));
+
+        complete(&requests, 0, Err("offline".into()));
+        harness.settle();
+        assert_eq!(harness.element().status(), Status::Failed);
+        assert_eq!(harness.element().error(), Some(&"offline".to_string()));
+        let html = harness.view().to_html();
+        assert!(html.contains("Échec du chargement"));
+        assert!(html.contains("Réessayer"));
+
+        harness.send(Msg::Retry);
+        assert!(harness.element().is_loading());
+        complete(&requests, 0, Ok(1));
+        harness.settle();
+        assert_eq!(harness.element().data(), Some(&1));
+    }
+
+    #[test]
+    fn test_refresh() {
+        let requests = Requests::default();
+        let mut harness = harness(resource(&requests));
+        complete(&requests, 0, Ok(1));
+        harness.settle().send(Msg::Refresh);
+        assert!(harness.element().is_refreshing());
+        assert_eq!(harness.element().data(), Some(&1));
+
+        complete(&requests, 0, Ok(2));
+        harness.settle();
+        assert!(!harness.element().is_refreshing());
+        assert_eq!(harness.element().data(), Some(&2));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let requests = Requests::default();
+        let mut harness = harness(resource(&requests));
+        harness.send(Msg::Retry);
+        assert_eq!(requests.borrow().len(), 2);
+
+        // the first request was replaced by the retry, so its result is ignored
+        complete(&requests, 0, Ok(1));
+        harness.settle();
+        assert!(harness.element().is_loading());
+
+        complete(&requests, 0, Ok(2));
+        harness.settle();
+        assert_eq!(harness.element().data(), Some(&2));
+    }
+}
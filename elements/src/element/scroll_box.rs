@@ -0,0 +1,294 @@
+//! ScrollBox element
+//!
+//! ScrollBox is a scroll container that the design system can style (e.g. thin
+//! scrollbars and shadows at the edges that still have content to scroll to).
+//! It reports the scroll position, tells its parent when the content is
+//! scrolled to the end, and can be scrolled programmatically.
+//!
+//! See [`ScrollBox`] docs to find out more about its methods.
+//!
+//! # Usage
+//!
+//! ScrollBox doesn't own its content, parents pass the content to
+//! `view_with` along with the function that maps the scroll box messages:
+//!
+//! ```rust
+//! use savory::prelude::*;
+//! use savory_elements::prelude::*;
+//! use savory_style::unit::px;
+//!
+//! pub enum Msg {
+//!     ScrollBox(scroll_box::Msg),
+//! }
+//!
+//! pub struct Feed {
+//!     items: Vec<String>,
+//!     scroll_box: ScrollBox,
+//! }
+//!
+//! impl Element for Feed {
+//!     type Message = Msg;
+//!     type Config = ();
+//!
+//!     fn init(_: (), orders: &mut impl Orders<Msg>, env: Env) -> Self {
+//!         Feed {
+//!             items: vec![],
+//!             scroll_box: ScrollBox::config()
+//!                 .max_height(px(400))
+//!                 .init(&mut orders.proxy(Msg::ScrollBox), env),
+//!         }
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             Msg::ScrollBox(msg) => {
+//!                 if let scroll_box::Msg::ReachedEnd = msg {
+//!                     // load more items
+//!                 }
+//!                 self.scroll_box.update(msg, &mut orders.proxy(Msg::ScrollBox));
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! impl View<Node<Msg>> for Feed {
+//!     fn view(&self) -> Node<Msg> {
+//!         let items = self.items.iter().map(|item| html::p_with(item.clone()));
+//!         self.scroll_box.view_with(items.collect::<Vec<_>>(), Msg::ScrollBox)
+//!     }
+//! }
+//! ```
+//!
+//! Parents can scroll the box by sending it `Msg::ScrollTo` or
+//! `Msg::ScrollIntoView`, the scrolling happens after the next render, so it
+//! works for content that is added in the same update.
+//!
+//! [`ScrollBox`]: crate::prelude::ScrollBox
+
+use crate::{id::Id, prelude::*};
+use derive_rich::Rich;
+use savory::{prelude::*, web_sys};
+use savory_style::{prelude::*, unit::Length};
+use wasm_bindgen::JsCast;
+
+pub enum Msg {
+    Rerender,
+    Mounted,
+    /// Sent by the scroll box when it gets scrolled
+    Scrolled(ScrollPosition),
+    /// Sent by the scroll box when the end of its content is scrolled into
+    /// view, it's sent again only after the scroll box leaves the end
+    ReachedEnd,
+    /// Scroll to the given offset from the top in pixels
+    ScrollTo(f64),
+    /// Scroll the element with the given id into view
+    ScrollIntoView(Id),
+}
+
+/// Scroll position and size of the scroll box
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ScrollPosition {
+    /// Scrolled distance from the top in pixels
+    pub top: f64,
+    /// Scrolled distance from the left in pixels
+    pub left: f64,
+    /// Height of the content in pixels
+    pub scroll_height: f64,
+    /// Height of the visible area in pixels
+    pub client_height: f64,
+}
+
+impl ScrollPosition {
+    fn of(el: &web_sys::Element) -> Self {
+        Self {
+            top: el.scroll_top() as f64,
+            left: el.scroll_left() as f64,
+            scroll_height: el.scroll_height() as f64,
+            client_height: el.client_height() as f64,
+        }
+    }
+
+    /// Distance between the bottom of the visible area and the end of the
+    /// content in pixels
+    pub fn remaining(&self) -> f64 {
+        (self.scroll_height - self.client_height - self.top).max(0.0)
+    }
+}
+
+#[derive(Element, Rich)]
+#[element(style_map(scroll_box))]
+pub struct ScrollBox {
+    // general element properties
+    #[rich(read)]
+    #[element(config)]
+    id: Option<Id>,
+    env: Env,
+    el_ref: ElRef<web_sys::Element>,
+
+    #[rich(read)]
+    #[element(config, data_lens(clone))]
+    max_height: Option<Length>,
+    /// `ReachedEnd` is sent when the remaining content is less than this
+    /// distance in pixels
+    #[rich(read(copy))]
+    #[element(config(default = "100.0"))]
+    end_threshold: f64,
+    #[rich(read(copy))]
+    position: ScrollPosition,
+    #[rich(read(copy, rename = is_at_start))]
+    #[element(data_lens)]
+    at_start: bool,
+    #[rich(read(copy, rename = is_at_end))]
+    #[element(data_lens)]
+    at_end: bool,
+    reached_end: bool,
+}
+
+impl ScrollBox {
+    /// Render the scroll box with `content`, `f` maps the scroll box messages
+    /// into the parent messages
+    pub fn view_with<PMsg: 'static>(
+        &self,
+        content: impl html::Children<PMsg>,
+        f: impl FnOnce(Msg) -> PMsg + Clone + 'static,
+    ) -> Node<PMsg> {
+        self.view().map_msg(f).push(content.into_children())
+    }
+
+    fn set_position(&mut self, position: ScrollPosition, orders: &mut impl Orders<Msg>) {
+        // one pixel tolerance for fractional scroll positions
+        self.at_start = position.top < 1.0;
+        self.at_end = position.remaining() < 1.0;
+        self.position = position;
+
+        let reached_end = position.remaining() <= self.end_threshold;
+        if reached_end && !self.reached_end {
+            orders.send_msg(Msg::ReachedEnd);
+        }
+        self.reached_end = reached_end;
+    }
+}
+
+impl Element for ScrollBox {
+    type Message = Msg;
+    type Config = Config;
+
+    fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<ScrollBox>| Msg::Rerender);
+
+        Self {
+            id: config.id,
+            env,
+            el_ref: ElRef::default(),
+            max_height: config.max_height,
+            end_threshold: config.end_threshold,
+            position: ScrollPosition::default(),
+            at_start: true,
+            at_end: true,
+            reached_end: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message, orders: &mut impl Orders<Msg>) {
+        match msg {
+            Msg::Rerender | Msg::ReachedEnd => {}
+            Msg::Mounted => {
+                if let Some(el) = self.el_ref.get() {
+                    self.set_position(ScrollPosition::of(&el), orders);
+                }
+            }
+            Msg::Scrolled(position) => self.set_position(position, orders),
+            Msg::ScrollTo(top) => {
+                let el_ref = self.el_ref.clone();
+                orders.after_next_render(move |_| {
+                    if let Some(el) = el_ref.get() {
+                        el.set_scroll_top(top as i32);
+                    }
+                });
+            }
+            Msg::ScrollIntoView(id) => {
+                orders.after_next_render(move |_| {
+                    if let Some(el) =
+                        savory::seed::browser::util::document().get_element_by_id(&id.to_string())
+                    {
+                        el.scroll_into_view();
+                    }
+                });
+            }
+        }
+    }
+
+    fn lifecycle(event: Lifecycle) -> Option<Msg> {
+        match event {
+            Lifecycle::Mounted => Some(Msg::Mounted),
+            _ => None,
+        }
+    }
+}
+
+impl View<Node<Msg>> for ScrollBox {
+    fn view(&self) -> Node<Msg> {
+        let style_map = self
+            .env
+            .designer::<ScrollBox>()
+            .design(self.data_lens(), &self.env);
+
+        html::div()
+            .try_id(self.id.clone())
+            .classes(
+                self.env
+                    .classes()
+                    .add("scroll-box")
+                    .add_if(self.at_start, "at-start")
+                    .add_if(self.at_end, "at-end"),
+            )
+            .style(style_map.scroll_box)
+            .el_ref(&self.el_ref)
+            .on_scroll(|event| {
+                event
+                    .target()
+                    .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                    .map(|el| Msg::Scrolled(ScrollPosition::of(&el)))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use savory::testing::{Call, Harness};
+
+    #[test]
+    fn test_reached_end() {
+        let mut harness = Harness::<ScrollBox>::new(ScrollBox::config());
+        // `ReachedEnd` is the only message the scroll box sends to itself
+        let mut scroll_to = |top: f64| {
+            harness.send(Msg::Scrolled(ScrollPosition {
+                top,
+                left: 0.0,
+                scroll_height: 1000.0,
+                client_height: 200.0,
+            }));
+            let sent = harness
+                .take_calls()
+                .into_iter()
+                .filter(|call| *call == Call::Send)
+                .count();
+            (sent, harness.element().is_at_end())
+        };
+
+        // far from the end
+        assert_eq!(scroll_to(0.0), (0, false));
+        // entering the 100px threshold sends `ReachedEnd` once
+        assert_eq!(scroll_to(700.0), (1, false));
+        assert_eq!(scroll_to(750.0), (0, false));
+        assert_eq!(scroll_to(800.0), (0, true));
+        // leaving the threshold re-arms it
+        assert_eq!(scroll_to(500.0), (0, false));
+        // fractional positions within 1px of the end are at the end
+        assert_eq!(scroll_to(799.5), (1, true));
+        assert_eq!(scroll_to(798.5), (0, false));
+    }
+}
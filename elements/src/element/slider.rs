@@ -22,6 +22,7 @@ pub enum Msg {
     Disable(bool),
     Value(f32),
     MoveValueTo(i32),
+    Mounted,
 }
 
 #[derive(Element, Rich)]
@@ -33,6 +34,7 @@ pub struct Slider {
     id: Option<Id>,
     env: Env,
     slider_ref: ElRef<web_sys::HtmlElement>,
+    mounted: bool,
 
     #[rich(read(copy))]
     #[element(config(default = "0.0", no_pub), data_lens)]
@@ -79,11 +81,10 @@ impl Slider {
             (x - in_min) * (out_max - out_min) / (in_max - in_min) + out_min
         }
 
-        let rect = self
-            .slider_ref
-            .get()
-            .expect("Slider is not rendered yet!, cannot get it.")
-            .get_bounding_client_rect();
+        let rect = match self.slider_ref.get() {
+            Some(slider) => slider.get_bounding_client_rect(),
+            None => return,
+        };
         let val = map_range(
             x as f64,
             (rect.left(), rect.right()),
@@ -99,12 +100,15 @@ impl Element for Slider {
     type Config = Config;
 
     fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
-        orders.subscribe(|_: RerenderRequested| Msg::Rerender);
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<Slider>| Msg::Rerender);
 
         Self {
             id: config.id,
             env,
             slider_ref: ElRef::default(),
+            mounted: false,
             value: config.value,
             max: config.max,
             min: config.min,
@@ -127,10 +131,19 @@ impl Element for Slider {
                 }
             }
             Msg::MoveValueTo(val) => {
-                if !self.disabled {
+                // slider position is only known after it's mounted
+                if !self.disabled && self.mounted {
                     self.move_by(val)
                 }
             }
+            Msg::Mounted => self.mounted = true,
+        }
+    }
+
+    fn lifecycle(event: Lifecycle) -> Option<Msg> {
+        match event {
+            Lifecycle::Mounted => Some(Msg::Mounted),
+            _ => None,
         }
     }
 }
@@ -142,7 +155,9 @@ impl View<Node<Msg>> for Slider {
             .designer::<Slider>()
             .design(self.data_lens(), &self.env);
 
-        let button = html::div().class("button").style(style_map.button);
+        let button = html::div()
+            .classes(self.env.classes().add("button"))
+            .style(style_map.button);
 
         let bar = html::div().style(style_map.bar).el_ref(&self.slider_ref);
         let indicator = html::div().style(style_map.indicator);
@@ -161,9 +176,14 @@ impl View<Node<Msg>> for Slider {
 
         html::div()
             .try_id(self.id.clone())
-            .class("slider")
+            .classes(self.env.classes().add("slider"))
             .style(style_map.slider)
             .tab_index(0)
+            .role(Role::Slider)
+            .aria_valuenow(self.value)
+            .aria_valuemin(self.min)
+            .aria_valuemax(self.max)
+            .aria_disabled(self.disabled)
             .push(bar)
             .push(indicator)
             .push(button)
@@ -35,6 +35,8 @@ pub enum Msg {
 #[element(style_map(switch, check_sign, text))]
 pub struct Switch {
     // general element properties
+    /// Element id, generated by the `IdGenerator` found in the environment
+    /// if it's not set
     #[rich(read)]
     #[element(config)]
     id: Option<Id>,
@@ -70,10 +72,12 @@ impl Element for Switch {
     type Config = Config;
 
     fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
-        orders.subscribe(|_: RerenderRequested| Msg::Rerender);
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<Switch>| Msg::Rerender);
 
         Self {
-            id: config.id,
+            id: config.id.or_else(|| env.try_generate_id("switch")),
             env,
             text: config.text,
             toggled: config.toggled,
@@ -104,24 +108,28 @@ impl View<Node<Msg>> for Switch {
             .designer::<Switch>()
             .design(self.data_lens(), &self.env);
         let switch = html::button()
-            .class("switch")
+            .classes(self.env.classes().add("switch"))
             .style(style_map.switch)
             .disabled(self.disabled)
-            // .checked(self.toggled)
-            // .type_("switch")
+            .role(Role::Switch)
+            .aria_checked(self.toggled)
             .on_focus(|_| Msg::Focus(true))
             .on_blur(|_| Msg::Focus(false))
             .on_mouse_enter(|_| Msg::MouseOver(true))
             .on_mouse_leave(|_| Msg::MouseOver(false))
             .on_click(|_| Msg::Toggle)
             // add `check-sign` node
-            .set(html::div().class("check-sign").style(style_map.check_sign));
+            .set(
+                html::div()
+                    .classes(self.env.classes().add("check-sign"))
+                    .style(style_map.check_sign),
+            );
 
         match self.text.as_ref() {
             None => switch.try_id(self.id.clone()),
             Some(lbl) => html::label()
                 .try_id(self.id.clone())
-                .class("text")
+                .classes(self.env.classes().add("text"))
                 .style(style_map.text)
                 .push(switch)
                 .push(lbl.clone())
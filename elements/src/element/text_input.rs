@@ -37,6 +37,8 @@ pub enum Msg {
 #[element(style_map)]
 pub struct TextInput {
     // general element properties
+    /// Element id, generated by the `IdGenerator` found in the environment
+    /// if it's not set
     #[rich(read)]
     #[element(config)]
     id: Option<Id>,
@@ -49,7 +51,7 @@ pub struct TextInput {
     text: Option<Cow<'static, str>>,
     #[rich(read(copy))]
     #[element(config)]
-    max_length: Option<i32>,
+    max_length: Option<u32>,
     #[rich(read)]
     #[element(config)]
     placeholder: Option<Cow<'static, str>>,
@@ -76,10 +78,12 @@ impl Element for TextInput {
     type Config = Config;
 
     fn init(config: Self::Config, orders: &mut impl Orders<Msg>, env: Env) -> Self {
-        orders.subscribe(|_: RerenderRequested| Msg::Rerender);
+        orders
+            .subscribe(|_: RerenderRequested| Msg::Rerender)
+            .subscribe_env(&env, |_: Designer<TextInput>| Msg::Rerender);
 
         Self {
-            id: config.id,
+            id: config.id.or_else(|| env.try_generate_id("text-input")),
             el_ref: ElRef::default(),
             env,
             text: config.text,
@@ -118,10 +122,9 @@ impl View<Node<Msg>> for TextInput {
             .design(self.data_lens(), &self.env);
 
         html::input()
-            .class("text-input")
+            .classes(self.env.classes().add("input"))
             .try_id(self.id.clone())
             .style(style)
-            .class("input")
             .disabled(self.disabled)
             .try_value(self.text.clone())
             .try_max_length(self.max_length)
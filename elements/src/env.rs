@@ -1,6 +1,10 @@
-use crate::prelude::{DataLens, Design, Designer, ViewStyle};
-use savory::prelude::Env;
-use std::rc::Rc;
+use crate::{
+    design_system::ClassPrefix,
+    id::{Id, IdGenerator},
+    prelude::{DataLens, Design, Designer, ViewStyle},
+};
+use savory::prelude::{Classes, Env};
+use std::{borrow::Cow, rc::Rc};
 
 pub trait EnvExt {
     fn insert_designer<T>(self, designer: Rc<dyn Design<T>>) -> Self
@@ -22,6 +26,21 @@ pub trait EnvExt {
         F: FnOnce(Designer<T>) -> Rc<dyn Design<T>>;
 
     fn designer<T: 'static>(&self) -> Designer<T>;
+
+    fn insert_class_prefix(self, prefix: impl Into<Cow<'static, str>>) -> Self;
+
+    /// Classes builder that uses the class prefix found in the environment
+    fn classes(&self) -> Classes;
+
+    /// Generate unique id using the `IdGenerator` found in the environment
+    ///
+    /// # Panics
+    ///
+    /// This panics if there is no `IdGenerator` in the environment, you can
+    /// use `try_generate_id` for non-panicing version.
+    fn generate_id(&self, name: &str) -> Id;
+
+    fn try_generate_id(&self, name: &str) -> Option<Id>;
 }
 
 impl EnvExt for Env {
@@ -61,4 +80,56 @@ impl EnvExt for Env {
             std::any::type_name::<Designer<T>>()
         ))
     }
+
+    fn insert_class_prefix(self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.insert(ClassPrefix(prefix.into()))
+    }
+
+    fn classes(&self) -> Classes {
+        match self.get::<ClassPrefix>() {
+            Some(ClassPrefix(prefix)) => Classes::with_prefix(prefix),
+            None => Classes::new(),
+        }
+    }
+
+    fn generate_id(&self, name: &str) -> Id {
+        self.try_generate_id(name).unwrap_or_else(|| {
+            panic!(
+                "{} isn't found in the environment",
+                std::any::type_name::<IdGenerator>()
+            )
+        })
+    }
+
+    fn try_generate_id(&self, name: &str) -> Option<Id> {
+        self.get_ref::<IdGenerator>()
+            .map(|generator| generator.generate(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use savory::{prelude::*, testing::Harness};
+
+    // unstyled designer, so the button can be rendered without a design system
+    struct Plain;
+
+    impl Design<Button> for Plain {
+        fn design(&self, _: button::ButtonLens, _: &Env) -> button::StyleMap {
+            button::StyleMap::default()
+        }
+    }
+
+    #[test]
+    fn test_class_prefix() {
+        let env = Env::base_branch()
+            .insert_designer(Rc::new(Plain) as Rc<dyn Design<Button>>)
+            .insert_class_prefix("sv-");
+        assert!(env.classes().add("button").contains("button"));
+
+        let button = Harness::<Button>::with_env(Button::config().text("Save"), env);
+        assert_eq!(button.view().get_class(), Some("sv-button"));
+    }
 }
@@ -1,3 +1,26 @@
+//! Element ids
+//!
+//! [`IdGenerator`] generates unique and readable ids (e.g. `switch-1`), it's
+//! stored in the root `Env` so elements can get ids from it in their `init`
+//! (`Switch`, `Radio` and `TextInput` use it for their default ids):
+//!
+//! ```rust
+//! use savory::prelude::*;
+//! use savory_elements::{id::IdGenerator, prelude::*};
+//!
+//! let env = Env::base_branch().insert(IdGenerator::with_prefix("app"));
+//! let branch = env.branch();
+//! assert_eq!(branch.generate_id("switch").to_string(), "app-switch-1");
+//! assert_eq!(env.generate_id("switch").to_string(), "app-switch-2");
+//! assert_eq!(branch.generate_id("label").to_string(), "app-label-1");
+//! ```
+//!
+//! Ids are generated in the order elements are initialized, so rendering the
+//! same view on the server and in the browser produces the same ids, as long
+//! as each render starts with a new `IdGenerator`.
+
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc};
+
 #[derive(Debug, Display, Clone, PartialEq, PartialOrd, Hash, From)]
 pub struct Id(String);
 
@@ -7,7 +30,8 @@ impl Id {
     /// by one element or view.
     ///
     /// when calling this function it's your responsibility to make sure this id
-    /// is only assigned to one element or view.
+    /// is only assigned to one element or view, use [`IdGenerator`] to get
+    /// unique ids instead.
     pub fn new(id: impl ToString) -> Id {
         Id(id.to_string())
     }
@@ -30,3 +54,79 @@ impl From<Id> for std::borrow::Cow<'static, str> {
         source.0.into()
     }
 }
+
+/// Generator of unique ids
+///
+/// Clones of the generator share the same counters, see [module docs](self)
+/// for more details.
+#[derive(Debug, Clone, Default)]
+pub struct IdGenerator {
+    prefix: Option<Cow<'static, str>>,
+    counters: Rc<RefCell<HashMap<String, u32>>>,
+}
+
+impl IdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create generator that adds `prefix` to the generated ids, which avoid
+    /// collisions with the ids used by the host page
+    pub fn with_prefix(prefix: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Generate new id that starts with `name`, e.g. `name-1`
+    pub fn generate(&self, name: &str) -> Id {
+        let mut counters = self.counters.borrow_mut();
+        let counter = counters.entry(name.to_string()).or_insert(0);
+        *counter += 1;
+        match self.prefix {
+            Some(ref prefix) => Id(format!("{}-{}-{}", prefix, name, counter)),
+            None => Id(format!("{}-{}", name, counter)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use savory::{prelude::*, testing::Harness};
+
+    fn init_ids(generator: IdGenerator) -> Vec<Option<Id>> {
+        let env = Env::base_branch().insert(generator);
+        let switch = Harness::<Switch>::with_env(Switch::config(), env.branch());
+        let radio = Harness::<Radio>::with_env(Radio::config(), env.branch());
+        let first = Harness::<TextInput>::with_env(TextInput::config(), env.branch());
+        let second = Harness::<TextInput>::with_env(TextInput::config(), env.branch());
+        let custom = Harness::<TextInput>::with_env(TextInput::config().id("name"), env);
+        vec![
+            switch.element().get_id().cloned(),
+            radio.element().get_id().cloned(),
+            first.element().get_id().cloned(),
+            second.element().get_id().cloned(),
+            custom.element().get_id().cloned(),
+        ]
+    }
+
+    #[test]
+    fn test_generated_ids_are_deterministic() {
+        let server = init_ids(IdGenerator::with_prefix("app"));
+        let client = init_ids(IdGenerator::with_prefix("app"));
+        assert_eq!(server, client);
+        assert_eq!(
+            server,
+            vec![
+                Some(Id::new("app-switch-1")),
+                Some(Id::new("app-radio-1")),
+                Some(Id::new("app-text-input-1")),
+                Some(Id::new("app-text-input-2")),
+                Some(Id::new("name")),
+            ]
+        );
+    }
+}
@@ -92,6 +92,12 @@ impl<Msg> From<Node<Msg>> for Item<Msg> {
     }
 }
 
+impl<T, Msg> From<html::HtmlEl<T, Msg>> for Item<Msg> {
+    fn from(source: html::HtmlEl<T, Msg>) -> Self {
+        Self::from(source.into_node())
+    }
+}
+
 impl<'a, Msg> From<&'a dyn View<Node<Msg>>> for Item<Msg> {
     fn from(source: &'a dyn View<Node<Msg>>) -> Self {
         Self::from(source.view())
@@ -143,12 +143,7 @@ impl<Msg> View<Node<Msg>> for Flex<Msg> {
                 .try_gap(self.gap.clone())
             })
             .class("flex")
-            .push(
-                self.items
-                    .iter()
-                    .map(|item| item.view())
-                    .collect::<Vec<Node<Msg>>>(),
-            )
+            .push_iter(self.items.iter().map(View::<Node<Msg>>::view))
     }
 }
 
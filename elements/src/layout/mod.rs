@@ -1 +1,2 @@
 pub mod flex;
+pub mod virtual_list;
@@ -0,0 +1,513 @@
+//! Virtualized list layout
+//!
+//! `VirtualList` renders only the items visible in its viewport plus a few
+//! items before and after them (overscan), the rest of the items are replaced
+//! by spacers that keep the scrollbar size and position correct. This keeps
+//! the view fast for lists with thousands of items.
+//!
+//! The list doesn't keep the scroll position by itself, its owner stores the
+//! position reported by `on_scroll` and passes it back using `scroll_top`.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use savory::prelude::*;
+//! use savory_elements::prelude::*;
+//!
+//! enum Msg {
+//!     Scrolled(f64),
+//! }
+//!
+//! struct Rows {
+//!     rows: Vec<String>,
+//!     scroll_top: f64,
+//! }
+//!
+//! impl View<Node<Msg>> for Rows {
+//!     fn view(&self) -> Node<Msg> {
+//!         let rows = self.rows.clone();
+//!         VirtualList::new(self.rows.len(), 24.0, move |index| {
+//!             html::div().push(rows[index].clone())
+//!         })
+//!         .height(240.0)
+//!         .scroll_top(self.scroll_top)
+//!         .on_scroll(Msg::Scrolled)
+//!         .view()
+//!     }
+//! }
+//! ```
+//!
+//! Items with different heights can use [`ItemHeights`], it stores the
+//! heights of the items and caches their offsets, items that haven't been
+//! measured yet use an estimated height:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! # use savory_elements::prelude::*;
+//! use savory_elements::layout::virtual_list::ItemHeights;
+//!
+//! let heights = ItemHeights::new(30.0);
+//! heights.set(0, 40.0);
+//! heights.set(1, 20.0);
+//! heights.set(2, 60.0);
+//!
+//! let list: VirtualList<()> =
+//!     VirtualList::new(1000, heights, |index| html::div().push(index.to_string()))
+//!         .height(100.0)
+//!         .scroll_top(70.0)
+//!         .overscan(1);
+//!
+//! // item 2 is the first visible item, and item 4 is the last one
+//! assert_eq!(list.visible_range(), 1..6);
+//! ```
+//!
+//! The list keeps a reference to the items it renders, so the owner can
+//! measure them after they are rendered using [`ItemHeights::measure`]:
+//!
+//! ```rust
+//! # use savory::prelude::*;
+//! # use savory_elements::prelude::*;
+//! use savory_elements::layout::virtual_list::ItemHeights;
+//!
+//! enum Msg {
+//!     Scrolled(f64),
+//!     Rendered,
+//! }
+//!
+//! struct Feed {
+//!     posts: Vec<String>,
+//!     heights: ItemHeights,
+//!     scroll_top: f64,
+//! }
+//!
+//! impl Element for Feed {
+//!     type Message = Msg;
+//!     type Config = Vec<String>;
+//!
+//!     fn init(posts: Vec<String>, orders: &mut impl Orders<Msg>, _: Env) -> Self {
+//!         orders.after_next_render(|_| Msg::Rendered);
+//!         Feed { posts, heights: ItemHeights::new(80.0), scroll_top: 0.0 }
+//!     }
+//!
+//!     fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) {
+//!         match msg {
+//!             Msg::Scrolled(top) => {
+//!                 self.scroll_top = top;
+//!                 orders.after_next_render(|_| Msg::Rendered);
+//!             }
+//!             // render again only if the measured heights changed
+//!             Msg::Rendered => match self.heights.measure() {
+//!                 true => {
+//!                     orders.after_next_render(|_| Msg::Rendered);
+//!                 }
+//!                 false => {
+//!                     orders.skip();
+//!                 }
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! impl View<Node<Msg>> for Feed {
+//!     fn view(&self) -> Node<Msg> {
+//!         let posts = self.posts.clone();
+//!         VirtualList::new(posts.len(), self.heights.clone(), move |index| {
+//!             html::p().push(posts[index].clone())
+//!         })
+//!         .scroll_top(self.scroll_top)
+//!         .on_scroll(Msg::Scrolled)
+//!         .view()
+//!     }
+//! }
+//! ```
+
+use crate::id::Id;
+use derive_rich::Rich;
+use savory::prelude::*;
+use savory::seed::virtual_dom::el_key;
+use savory_style::{prelude::*, unit::px, St};
+use std::{cell::RefCell, ops::Range, rc::Rc};
+use wasm_bindgen::JsCast;
+
+type ItemView<Msg> = Rc<dyn Fn(usize) -> Vec<Node<Msg>>>;
+type OnScroll<Msg> = Rc<dyn Fn(f64) -> Msg>;
+
+/// Height of the list items in pixels
+#[derive(Clone)]
+pub enum ItemHeight {
+    /// All items have the same height
+    Fixed(f64),
+    /// Items have different heights
+    Measured(ItemHeights),
+}
+
+impl From<f64> for ItemHeight {
+    fn from(source: f64) -> Self {
+        ItemHeight::Fixed(source)
+    }
+}
+
+impl From<ItemHeights> for ItemHeight {
+    fn from(source: ItemHeights) -> Self {
+        ItemHeight::Measured(source)
+    }
+}
+
+/// Heights of list items that have different heights
+///
+/// This is a shared handle, clones refer to the same heights, the owner of
+/// the list keeps one and passes clones to the list on every view. Offsets of
+/// the items are cached and only recalculated after the height of an item
+/// before them changes.
+///
+/// See [module docs](self) for more details.
+#[derive(Clone)]
+pub struct ItemHeights(Rc<RefCell<Heights>>);
+
+struct Heights {
+    estimated: f64,
+    measured: Vec<Option<f64>>,
+    // `offsets[index]` is the sum of the heights of the items before `index`,
+    // only the first `offsets.len()` offsets are valid
+    offsets: Vec<f64>,
+    // items rendered by the last view
+    rendered: Vec<(usize, ElRef<web_sys::Element>)>,
+}
+
+impl Heights {
+    fn height(&self, index: usize) -> f64 {
+        self.measured
+            .get(index)
+            .copied()
+            .flatten()
+            .unwrap_or(self.estimated)
+    }
+
+    /// Make sure the offsets of the items `0..=len` are valid
+    fn cache_offsets(&mut self, len: usize) {
+        if self.offsets.is_empty() {
+            self.offsets.push(0.0);
+        }
+        while self.offsets.len() <= len {
+            let index = self.offsets.len() - 1;
+            let offset = self.offsets[index] + self.height(index);
+            self.offsets.push(offset);
+        }
+    }
+}
+
+impl ItemHeights {
+    /// Create heights where items that are not measured yet have `estimated`
+    /// height
+    pub fn new(estimated: f64) -> Self {
+        Self(Rc::new(RefCell::new(Heights {
+            estimated: estimated.max(1.0),
+            measured: vec![],
+            offsets: vec![],
+            rendered: vec![],
+        })))
+    }
+
+    /// Height of the item at `index`
+    pub fn get(&self, index: usize) -> f64 {
+        self.0.borrow().height(index)
+    }
+
+    /// Set the height of the item at `index`, returns `true` if the height
+    /// have changed
+    pub fn set(&self, index: usize, height: f64) -> bool {
+        let mut heights = self.0.borrow_mut();
+        if heights.measured.get(index).copied().flatten() == Some(height) {
+            return false;
+        }
+        if heights.measured.len() <= index {
+            heights.measured.resize(index + 1, None);
+        }
+        heights.measured[index] = Some(height);
+        // offsets of the items after `index` are no longer valid
+        heights.offsets.truncate(index + 1);
+        true
+    }
+
+    /// Measure the items rendered by the last view and update their heights,
+    /// returns `true` if any height have changed
+    ///
+    /// This should be called after the list is rendered (e.g. in a message
+    /// sent using `orders.after_next_render`), it does nothing outside the
+    /// browser.
+    pub fn measure(&self) -> bool {
+        let mut measured: Vec<(usize, f64)> = vec![];
+        for (index, el_ref) in self.0.borrow().rendered.iter() {
+            if let Some(el) = el_ref.get() {
+                let height = el.get_bounding_client_rect().height();
+                match measured.last_mut() {
+                    Some((last, sum)) if last == index => *sum += height,
+                    _ => measured.push((*index, height)),
+                }
+            }
+        }
+        measured
+            .into_iter()
+            .fold(false, |changed, (index, height)| {
+                self.set(index, height) || changed
+            })
+    }
+
+    /// Offset of the item at `index` from the top of the list
+    fn offset(&self, index: usize) -> f64 {
+        let mut heights = self.0.borrow_mut();
+        heights.cache_offsets(index);
+        heights.offsets[index]
+    }
+
+    /// Index of the first item that ends after `offset` and the first item
+    /// that starts at or after `end`
+    fn range(&self, len: usize, offset: f64, end: f64) -> (usize, usize) {
+        let mut heights = self.0.borrow_mut();
+        heights.cache_offsets(len);
+        let offsets = &heights.offsets[..=len];
+        let first = offsets[1..].partition_point(|item_end| *item_end <= offset);
+        let last = offsets[..len].partition_point(|item_start| *item_start < end);
+        (first, last)
+    }
+
+    /// Keep a reference to the item node at `index`, so it can be measured
+    /// after it's rendered
+    fn track<Msg>(&self, index: usize, node: &mut Node<Msg>) {
+        if let Node::Element(ref mut el) = node {
+            let el_ref = ElRef::default();
+            el.refs.push(el_ref.clone().shared_node_ws);
+            self.0.borrow_mut().rendered.push((index, el_ref));
+        }
+    }
+}
+
+/// Virtual list layout
+///
+/// See [module docs](self) for more details.
+#[derive(Clone, Rich)]
+pub struct VirtualList<Msg> {
+    #[rich(write)]
+    pub id: Option<Id>,
+    /// Number of items in the list
+    pub len: usize,
+    #[rich(write)]
+    pub item_height: ItemHeight,
+    /// Viewport height in pixels, defaults to 400
+    #[rich(write)]
+    pub height: f64,
+    /// Current scroll position of the viewport in pixels
+    #[rich(write)]
+    pub scroll_top: f64,
+    /// Number of items rendered before and after the visible items, defaults
+    /// to 3
+    pub overscan: usize,
+    item_view: ItemView<Msg>,
+    on_scroll: Option<OnScroll<Msg>>,
+}
+
+/// Rendered items and the spacers heights
+struct Window {
+    range: Range<usize>,
+    before: f64,
+    after: f64,
+}
+
+impl<Msg> VirtualList<Msg> {
+    /// Create list of `len` items, `item_view` is called with the index of
+    /// each rendered item
+    pub fn new<V>(
+        len: usize,
+        item_height: impl Into<ItemHeight>,
+        item_view: impl Fn(usize) -> V + 'static,
+    ) -> Self
+    where
+        V: html::Children<Msg>,
+    {
+        Self {
+            id: None,
+            len,
+            item_height: item_height.into(),
+            height: 400.0,
+            scroll_top: 0.0,
+            overscan: 3,
+            item_view: Rc::new(move |index| item_view(index).into_children()),
+            on_scroll: None,
+        }
+    }
+
+    pub fn overscan(mut self, val: usize) -> Self {
+        self.overscan = val;
+        self
+    }
+
+    /// Send the message returned by `f` with the new scroll position when the
+    /// viewport get scrolled
+    pub fn on_scroll(mut self, f: impl Fn(f64) -> Msg + 'static) -> Self {
+        self.on_scroll = Some(Rc::new(f));
+        self
+    }
+
+    /// Indexes of the rendered items, including the overscan items
+    pub fn visible_range(&self) -> Range<usize> {
+        self.window().range
+    }
+
+    fn window(&self) -> Window {
+        let total = match self.item_height {
+            ItemHeight::Fixed(height) => self.len as f64 * height.max(1.0),
+            ItemHeight::Measured(ref heights) => heights.offset(self.len),
+        };
+        // positions past the end (e.g. the list got shorter) show the end of
+        // the list
+        let scroll_top = self.scroll_top.min(total - self.height).max(0.0);
+        let viewport_end = scroll_top + self.height;
+        let (first, last) = match self.item_height {
+            ItemHeight::Fixed(height) => {
+                let height = height.max(1.0);
+                (
+                    ((scroll_top / height).floor() as usize).min(self.len),
+                    ((viewport_end / height).ceil() as usize).min(self.len),
+                )
+            }
+            ItemHeight::Measured(ref heights) => heights.range(self.len, scroll_top, viewport_end),
+        };
+        let start = first.saturating_sub(self.overscan);
+        let end = (last + self.overscan).min(self.len);
+        let offset = |index: usize| match self.item_height {
+            ItemHeight::Fixed(height) => index as f64 * height.max(1.0),
+            ItemHeight::Measured(ref heights) => heights.offset(index),
+        };
+        Window {
+            before: offset(start),
+            after: total - offset(end),
+            range: start..end,
+        }
+    }
+}
+
+impl<Msg: 'static> View<Node<Msg>> for VirtualList<Msg> {
+    fn view(&self) -> Node<Msg> {
+        let window = self.window();
+        if let ItemHeight::Measured(ref heights) = self.item_height {
+            heights.0.borrow_mut().rendered.clear();
+        }
+        let spacer = |height: f64| {
+            html::div()
+                .class("virtual-list-spacer")
+                .and_style(|s| s.and_size(|s| s.height(px(height as f32))))
+        };
+        let items = window.range.flat_map(|index| {
+            // keys let Seed move the rendered items while scrolling instead of
+            // patching all of them
+            (self.item_view)(index)
+                .into_iter()
+                .enumerate()
+                .map(move |(position, mut node)| {
+                    if let Node::Element(ref mut el) = node {
+                        el.key = Some(el_key(&format!("{}-{}", index, position)));
+                    }
+                    if let ItemHeight::Measured(ref heights) = self.item_height {
+                        heights.track(index, &mut node);
+                    }
+                    node
+                })
+        });
+
+        html::div()
+            .try_id(self.id.clone())
+            .class("virtual-list")
+            .and_style(|s| {
+                s.and_size(|s| s.height(px(self.height as f32)))
+                    .push(St::OverflowY, "auto")
+            })
+            .config(|node| match self.on_scroll.clone() {
+                Some(on_scroll) => {
+                    node.on_scroll(move |event| scroll_top(&event).map(|top| on_scroll(top)))
+                }
+                None => node,
+            })
+            .push(spacer(window.before))
+            .push_iter(items)
+            .push(spacer(window.after))
+    }
+}
+
+fn scroll_top(event: &web_sys::Event) -> Option<f64> {
+    event
+        .target()?
+        .dyn_into::<web_sys::Element>()
+        .ok()
+        .map(|el| el.scroll_top() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(len: usize, item_height: impl Into<ItemHeight>) -> VirtualList<()> {
+        VirtualList::new(len, item_height, |index| {
+            html::div().push(index.to_string())
+        })
+        .height(50.0)
+        .overscan(0)
+    }
+
+    fn measured() -> ItemHeights {
+        let heights = ItemHeights::new(10.0);
+        heights.set(0, 30.0);
+        heights
+    }
+
+    #[test]
+    fn test_end_of_list() {
+        let fixed = list(100, 10.0).scroll_top(950.0);
+        assert_eq!(fixed.visible_range(), 95..100);
+        assert_eq!(fixed.window().before, 950.0);
+        assert_eq!(fixed.window().after, 0.0);
+
+        let different = list(100, measured()).scroll_top(970.0);
+        assert_eq!(different.visible_range(), 95..100);
+        assert_eq!(different.window().before, 970.0);
+        assert_eq!(different.window().after, 0.0);
+    }
+
+    #[test]
+    fn test_scroll_past_end() {
+        let fixed = list(100, 10.0).scroll_top(5000.0);
+        assert_eq!(fixed.visible_range(), 95..100);
+        assert_eq!(fixed.overscan(2).visible_range(), 93..100);
+
+        let different = list(100, measured()).scroll_top(5000.0);
+        assert_eq!(different.visible_range(), 95..100);
+        assert_eq!(different.overscan(2).visible_range(), 93..100);
+
+        // lists shorter than the viewport show all their items
+        assert_eq!(list(3, 10.0).scroll_top(30.0).visible_range(), 0..3);
+        assert_eq!(list(3, measured()).scroll_top(30.0).visible_range(), 0..3);
+    }
+
+    #[test]
+    fn test_empty_list() {
+        for empty in [list(0, 10.0), list(0, measured())] {
+            let list = empty.scroll_top(100.0).overscan(3);
+            assert_eq!(list.visible_range(), 0..0);
+            assert_eq!(list.window().before, 0.0);
+            assert_eq!(list.window().after, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_height_change_invalidates_offsets() {
+        let heights = measured();
+        assert_eq!(heights.offset(60), 620.0);
+
+        assert!(heights.set(50, 100.0));
+        assert!(!heights.set(50, 100.0));
+        assert_eq!(heights.offset(50), 520.0);
+        assert_eq!(heights.offset(60), 710.0);
+
+        let list = list(100, heights.clone()).scroll_top(520.0);
+        assert_eq!(list.visible_range(), 50..51);
+    }
+}
@@ -63,10 +63,6 @@ pub mod view;
 
 pub use self::prelude::*;
 
-// TODO: add:
-// - ScrollBox layout element
-// - Slider element
-
 /// savory_elements prelude
 pub mod prelude {
     pub use super::{
@@ -78,17 +74,23 @@ pub mod prelude {
             button::{self, Button},
             progress_bar::{self, ProgressBar},
             radio::{self, Radio},
+            resource::{self, Resource},
+            scroll_box::{self, ScrollBox},
             slider::{self, Slider},
             switch::{self, Switch},
             text_input::{self, TextInput},
         },
         env::EnvExt,
         // id::Id,
-        layout::flex::{self, Flex},
+        layout::{
+            flex::{self, Flex},
+            virtual_list::{self, VirtualList},
+        },
         rerender::RerenderRequested,
         traits::ExtendBuilder,
         view::{
             image::{self, Image},
+            portal::{self, Portal, PortalHandle, PortalTarget},
             svg::{self, Svg},
             text::{self, Text},
         },
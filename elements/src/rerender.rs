@@ -1,6 +1,8 @@
 /// Subscribe to rerender requests
 ///
-/// This is used to request rerender for all elements, it's used after app
-/// change design system.
+/// This is used to request rerender for all elements. Elements rerender
+/// automatically when their designer changes in the `Env` (see
+/// `OrdersExt::subscribe_env`), so this is only needed for changes that are
+/// not tracked by the `Env`.
 #[derive(Clone)]
 pub struct RerenderRequested;
@@ -25,7 +25,7 @@ impl Image {
 
 impl<Msg> View<Node<Msg>> for Image {
     fn view(&self) -> Node<Msg> {
-        html::image()
+        html::img()
             .try_id(self.id.clone())
             .class("image")
             .src(self.src.clone())
@@ -1,5 +1,6 @@
 //! Reusable views
 
 pub mod image;
+pub mod portal;
 pub mod svg;
 pub mod text;
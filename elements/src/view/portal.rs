@@ -0,0 +1,67 @@
+//! Portal view
+//!
+//! Portal renders its children into a different DOM node (e.g. `body`), this
+//! is useful for dropdowns, tooltips and modals that need to escape parents
+//! with `overflow: hidden`. Events triggered inside the portal are still
+//! handled by the element that renders the portal.
+//!
+//! The portal itself is a [`PortalHandle`] owned by the element, the element
+//! creates it in `init` and the portal is removed once the element drops it.
+//!
+//! See [`savory::portal`] for more details.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use savory::prelude::*;
+//! use savory_elements::prelude::*;
+//!
+//! enum Msg {
+//!     Close,
+//! }
+//!
+//! // usually created in `Element::init` and stored in the element
+//! let overlays = PortalHandle::new(PortalTarget::id("overlays"));
+//!
+//! let modal: Node<Msg> = Portal::new(&overlays)
+//!     .push(html::button().on_click(|_| Msg::Close).push("Close"))
+//!     .into_node();
+//! ```
+use derive_rich::Rich;
+use savory::prelude::*;
+
+pub use savory::portal::{PortalHandle, PortalTarget};
+
+/// Portal view type
+#[derive(Rich)]
+pub struct Portal<'a, Msg: 'static> {
+    /// Portal the children get rendered into
+    pub handle: &'a PortalHandle<Msg>,
+    #[rich(write)]
+    pub children: Vec<Node<Msg>>,
+}
+
+impl<'a, Msg> Portal<'a, Msg> {
+    pub fn new(handle: &'a PortalHandle<Msg>) -> Self {
+        Self {
+            handle,
+            children: vec![],
+        }
+    }
+
+    pub fn push(mut self, child: impl Into<Node<Msg>>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Return the placeholder node, the children are moved into the portal
+    pub fn into_node(self) -> Node<Msg> {
+        self.handle.view(self.children)
+    }
+}
+
+impl<'a, Msg: 'static> From<Portal<'a, Msg>> for Node<Msg> {
+    fn from(source: Portal<'a, Msg>) -> Self {
+        source.into_node()
+    }
+}
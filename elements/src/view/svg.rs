@@ -3,10 +3,29 @@
 //! Svg is used to display SVG from a given SVG nodes
 //!
 //! # Usage
-//! TODO
+//!
+//! ```rust
+//! use savory::{prelude::*, svg::PathData};
+//! use savory_elements::prelude::*;
+//!
+//! // build the icon using typed SVG nodes
+//! let close = Svg::<()>::new(vec![savory::svg::path()
+//!     .d(PathData::new().move_to(6.0, 6.0).line_to(18.0, 18.0))
+//!     .into()]);
+//!
+//! // or parse it from SVG markup
+//! let icon = Svg::<()>::from_html(
+//!     r#"<svg viewBox="0 0 24 24"><circle cx="12" cy="12" r="10"/></svg>"#,
+//! )
+//! .unwrap();
+//! assert_eq!(icon.draw.len(), 1);
+//! ```
 use crate::id::Id;
 use derive_rich::Rich;
-use savory::prelude::*;
+use savory::{
+    prelude::*,
+    svg::{self, ParseError, ViewBox},
+};
 
 /// Svg view type
 #[derive(Clone, Rich)]
@@ -14,24 +33,47 @@ pub struct Svg<Msg> {
     #[rich(write)]
     pub id: Option<Id>,
     #[rich(write)]
+    pub view_box: Option<ViewBox>,
+    #[rich(write)]
     pub draw: Vec<Node<Msg>>,
 }
 
-// TODO: add from_html function
 impl<Msg> Svg<Msg> {
     pub fn new(draw: impl IntoIterator<Item = Node<Msg>>) -> Self {
         Self {
             id: None,
+            view_box: None,
             draw: draw.into_iter().collect(),
         }
     }
+
+    /// Create `Svg` from SVG markup
+    ///
+    /// If the markup has `<svg>` root element, its children are used as the
+    /// drawing and its `viewBox` is kept, otherwise all the parsed nodes are
+    /// used as the drawing.
+    pub fn from_html(source: &str) -> Result<Self, ParseError> {
+        let mut nodes = svg::parse(source)?;
+        match nodes.as_mut_slice() {
+            [Node::Element(root)] if root.tag.as_str() == "svg" => Ok(Self {
+                id: None,
+                view_box: root
+                    .get_attr("viewBox")
+                    .and_then(|view_box| view_box.parse().ok()),
+                draw: std::mem::take(&mut root.children),
+            }),
+            _ => Ok(Self::new(nodes)),
+        }
+    }
 }
 
 impl<Msg> View<Node<Msg>> for Svg<Msg> {
     fn view(&self) -> Node<Msg> {
-        html::svg()
+        svg::svg()
             .try_id(self.id.clone())
             .class("svg")
+            .try_view_box(self.view_box)
             .push(self.draw.clone())
+            .into()
     }
 }
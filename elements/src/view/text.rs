@@ -144,7 +144,7 @@ impl<Msg> View<Node<Msg>> for Text {
                 None => p,
             })
             .try_id(self.id.clone())
-            .class("text")
+            .classes(self.env.classes().add("text"))
             .style(style)
             .push(self.text.clone())
     }
@@ -1,5 +1,6 @@
 use super::{St, StyleValues, UpdateStyleValues};
 use palette::{Hsl, Hsla, LinSrgb, LinSrgba};
+use savory::svg;
 
 pub fn display_rgb(rgb: &LinSrgb) -> String {
     let (red, green, blue) = rgb.into_components();
@@ -359,6 +360,12 @@ pub enum Color {
     YellowGreen,
 }
 
+impl From<Color> for svg::Paint {
+    fn from(source: Color) -> Self {
+        svg::Paint::Color(source.to_string())
+    }
+}
+
 impl UpdateStyleValues for Color {
     fn update_style_values(self, values: StyleValues) -> StyleValues {
         values.add(St::Color, self)
@@ -53,5 +53,8 @@ pub use self::{
 };
 
 pub mod prelude {
-    pub use crate::{node::StyleApi, Style};
+    pub use crate::{
+        node::{MatchStyleExt, StyleApi},
+        Style,
+    };
 }
@@ -1,6 +1,8 @@
-use crate::Style;
+use crate::{Style, StyleValues};
 use savory::{
+    html::HtmlEl,
     prelude::{AndEl, El, Node},
+    query::Match,
     seed::prelude::UpdateEl,
 };
 
@@ -64,3 +66,49 @@ impl<Msg> StyleApi for Node<Msg> {
         self.and_el(|el| el.and_style(conf))
     }
 }
+
+impl<T, Msg> StyleApi for HtmlEl<T, Msg> {
+    fn style(self, style: Style) -> Self {
+        self.and_el(|el| el.style(style))
+    }
+
+    fn try_style(self, style: Option<Style>) -> Self {
+        self.and_el(|el| el.try_style(style))
+    }
+
+    fn replace_style(self, style: Style) -> Self {
+        self.and_el(|el| el.replace_style(style))
+    }
+
+    fn try_replace_style(self, style: Option<Style>) -> Self {
+        self.and_el(|el| el.try_replace_style(style))
+    }
+
+    fn and_style(self, conf: impl FnOnce(Style) -> Style) -> Self {
+        self.and_el(|el| el.and_style(conf))
+    }
+}
+
+/// Extension trait that returns the inline style of queried elements as
+/// `StyleValues`
+///
+/// ```
+/// use savory::prelude::*;
+/// use savory_style::{prelude::*, values as val, St, Style};
+///
+/// let node: Node<()> = html::div()
+///     .class("card")
+///     .style(Style::default().display(val::Flex));
+///
+/// let card = node.query(".card").unwrap();
+/// assert_eq!(card.style_values().get(&St::Display), Some("flex"));
+/// ```
+pub trait MatchStyleExt {
+    fn style_values(&self) -> StyleValues;
+}
+
+impl<'a, Msg> MatchStyleExt for Match<'a, Msg> {
+    fn style_values(&self) -> StyleValues {
+        StyleValues(self.style())
+    }
+}